@@ -0,0 +1,19 @@
+use openapiv3::{Header, Parameter, RequestBody, Response};
+
+/// A reusable object for `components.parameters`, `components.responses`,
+/// `components.requestBodies` or `components.headers`.
+pub enum Component {
+    Parameter(Parameter),
+    Response(Response),
+    RequestBody(RequestBody),
+    Header(Header),
+}
+
+pub struct ComponentRegister {
+    pub name: &'static str,
+    /// Module the component was registered from. Used to report name collisions.
+    pub module_path: &'static str,
+    pub constructor: &'static (dyn Sync + Send + Fn() -> Component),
+}
+
+inventory::collect!(ComponentRegister);
@@ -1,6 +1,8 @@
+mod component;
 mod operation;
 mod schema;
 
+pub use component::*;
 pub use operation::*;
 pub use schema::*;
 pub use openapiv3::*;
\ No newline at end of file
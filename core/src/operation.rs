@@ -1,10 +1,97 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
 use openapiv3::{Operation, Parameter, RefOr, Schema};
 
-pub struct OperationRegister {
-    pub name: &'static str,
-    pub constructor: &'static (dyn Sync + Send + Fn() -> Operation),
+/// The operation of a handler. `#[oasgen]` implements it on a type private to the handler, and ties the two
+/// together through the handler's return type, `Documented`.
+pub trait OaOperation {
+    fn operation() -> Operation;
+}
+
+/// The output of a handler (usually its future), tagged with the `OaOperation` documenting the handler.
+///
+/// It's transparent to web frameworks: it's a future if the output is, and awaiting it awaits the output.
+pub struct Documented<O, T> {
+    inner: T,
+    operation: PhantomData<fn() -> O>,
+}
+
+impl<O, T> Documented<O, T> {
+    pub fn new(_operation: O, inner: T) -> Self {
+        Documented { inner, operation: PhantomData }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<O, T> std::ops::Deref for Documented<O, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<O, T: Future> Future for Documented<O, T> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T::Output> {
+        // SAFETY: `inner` is never moved out of a pinned `Documented`, which has no `Drop` impl, and is
+        // only `Unpin` if `inner` is.
+        unsafe { self.map_unchecked_mut(|d| &mut d.inner) }.poll(cx)
+    }
 }
 
+/// A handler annotated with `#[oasgen]`, taking `Args`. The server looks up the operation of a handler through
+/// this trait, so routing a handler without an operation is a compile error.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not documented with an operation",
+    label = "not annotated with #[oasgen]",
+    note = "annotate the handler with #[oasgen]; a method taking `self` also needs #[oasgen] on its impl block",
+)]
+pub trait OaHandler<Args> {
+    type Operation: OaOperation;
+}
+
+macro_rules! impl_oa_handler {
+    ($($arg:ident),*) => {
+        impl<F, O, T, $($arg,)*> OaHandler<($($arg,)*)> for F
+        where
+            F: FnOnce($($arg),*) -> Documented<O, T>,
+            O: OaOperation,
+        {
+            type Operation = O;
+        }
+    };
+}
+
+impl_oa_handler!();
+impl_oa_handler!(A1);
+impl_oa_handler!(A1, A2);
+impl_oa_handler!(A1, A2, A3);
+impl_oa_handler!(A1, A2, A3, A4);
+impl_oa_handler!(A1, A2, A3, A4, A5);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15);
+impl_oa_handler!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16);
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be documented as an argument or output of a handler",
+    note = "implement `OaSchema` for the type, or `OaParameter` for an extractor",
+)]
 pub trait OaParameter {
     fn body_schema() -> Option<RefOr<Schema>> {
         None
@@ -25,5 +112,3 @@ where
         T::body_schema()
     }
 }
-
-inventory::collect!(OperationRegister);
\ No newline at end of file
@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use openapiv3::{ReferenceOr, Schema};
+use openapiv3::{ReferenceOr, Schema, SchemaData, SchemaKind};
 
 #[cfg(feature = "actix")]
 mod actix;
@@ -18,14 +16,48 @@ mod phonenumber;
 mod sqlx;
 #[cfg(feature = "time")]
 mod time;
-
-#[cfg(feature = "bigdecimal")]
-mod bigdecimal;
+#[cfg(feature = "chrono-tz")]
+mod chrono_tz;
+#[cfg(feature = "url")]
+mod url;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "semver")]
+mod semver;
+#[cfg(feature = "ulid")]
+mod ulid;
+#[cfg(feature = "email_address")]
+mod email_address;
+#[cfg(feature = "jiff")]
+mod jiff;
+#[cfg(feature = "geo-types")]
+mod geo_types;
+#[cfg(feature = "mime")]
+mod mime;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
+#[cfg(feature = "serde_with")]
+mod serde_with;
+
+#[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
+mod decimal;
 mod http;
+mod number;
+mod stdlib;
+mod map;
 #[cfg(feature = "sid")]
 mod sid;
 mod tuple;
 
+pub use map::{MapSchema, KEY_SCHEMA_EXTENSION};
+
+/// Extension marking the array schema of a tuple struct, whose `items` is an `anyOf` of the item
+/// schemas in order. OpenAPI 3.0 has no keyword for positional items, so they're converted to
+/// `prefixItems` for OpenAPI 3.1 and JSON Schema, and the marker is left out of 3.0 output.
+pub const PREFIX_ITEMS_EXTENSION: &str = "x-prefixItems";
+
 pub trait OaSchema {
     fn schema() -> Schema;
 
@@ -39,13 +71,36 @@ pub trait OaSchema {
     }
 }
 
+/// The schema of `T` when it's serialized with the adapter `Self`, i.e. a `serde_with` type used in
+/// `#[serde_as(as = "...")]`. The derive uses it for fields with a `serde_as` annotation.
+pub trait OaSchemaAs<T: ?Sized> {
+    fn schema() -> Schema;
+
+    fn schema_ref() -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Self::schema())
+    }
+}
+
 pub struct SchemaRegister {
     pub name: &'static str,
+    /// Module the schema was registered from. Used to report name collisions.
+    pub module_path: &'static str,
     pub constructor: &'static (dyn Sync + Send + Fn() -> Schema),
 }
 
 inventory::collect!(SchemaRegister);
 
+/// The schema of a remote type, registered by a `#[serde(remote = "...")]` definition deriving `OaSchema`.
+/// Fields serialized `#[serde(with = "...")]` look their type up here.
+pub struct RemoteRegister {
+    /// `std::any::type_name` of the remote type.
+    pub type_name: fn() -> &'static str,
+    pub schema: fn() -> Schema,
+    pub schema_ref: fn() -> ReferenceOr<Schema>,
+}
+
+inventory::collect!(RemoteRegister);
+
 #[macro_export]
 macro_rules! impl_oa_schema {
     ($t:ty,$schema:expr) => {
@@ -77,24 +132,6 @@ macro_rules! impl_oa_schema_passthrough {
 
 impl_oa_schema!(bool, Schema::new_bool());
 
-impl_oa_schema!(usize, Schema::new_integer());
-impl_oa_schema!(isize, Schema::new_integer());
-
-impl_oa_schema!(u8, Schema::new_integer());
-impl_oa_schema!(i8, Schema::new_integer());
-
-impl_oa_schema!(u16, Schema::new_integer());
-impl_oa_schema!(i16, Schema::new_integer());
-
-impl_oa_schema!(u32, Schema::new_integer());
-impl_oa_schema!(i32, Schema::new_integer());
-
-impl_oa_schema!(u64, Schema::new_integer());
-impl_oa_schema!(i64, Schema::new_integer());
-
-impl_oa_schema!(f32, Schema::new_number());
-impl_oa_schema!(f64, Schema::new_number());
-
 impl_oa_schema!(String, Schema::new_string());
 
 impl<T> OaSchema for Vec<T>
@@ -112,6 +149,9 @@ where
     }
 }
 
+/// `Option<Option<T>>` has the same schema as `Option<T>`: the field is optional and nullable, and the schema
+/// can't say that absent and `null` mean different things. Serde only tells them apart with a
+/// `deserialize_with` like `serde_with::rust::double_option`, so describe the difference in the field's docs.
 impl<T> OaSchema for Option<T>
 where
     T: OaSchema,
@@ -123,12 +163,24 @@ where
     }
 
     fn schema_ref() -> ReferenceOr<Schema> {
-        let mut schema = T::schema_ref();
-        let Some(s) = schema.as_mut() else {
-            return schema;
-        };
-        s.nullable = true;
-        schema
+        nullable(T::schema_ref())
+    }
+}
+
+/// Mark a schema nullable. A reference can't be marked nullable, so it's wrapped in `allOf`.
+pub(crate) fn nullable(schema: ReferenceOr<Schema>) -> ReferenceOr<Schema> {
+    match schema {
+        ReferenceOr::Item(mut schema) => {
+            schema.nullable = true;
+            ReferenceOr::Item(schema)
+        }
+        reference => ReferenceOr::Item(Schema {
+            data: SchemaData {
+                nullable: true,
+                ..SchemaData::default()
+            },
+            kind: SchemaKind::AllOf { all_of: vec![reference] },
+        }),
     }
 }
 
@@ -141,19 +193,6 @@ impl OaSchema for () {
     }
 }
 
-impl<K, V> OaSchema for HashMap<K, V>
-where
-    V: OaSchema,
-{
-    fn schema() -> Schema {
-        Schema::new_map(V::schema())
-    }
-
-    fn schema_ref() -> ReferenceOr<Schema> {
-        ReferenceOr::Item(Schema::new_map(V::schema_ref()))
-    }
-}
-
 #[cfg(feature = "uuid")]
 impl_oa_schema!(uuid::Uuid, Schema::new_string().with_format("uuid"));
 
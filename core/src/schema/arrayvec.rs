@@ -0,0 +1,34 @@
+use openapiv3::{ReferenceOr, Schema, SchemaKind, Type};
+
+use crate::OaSchema;
+
+fn with_max_items(mut schema: Schema, max: usize) -> Schema {
+    if let SchemaKind::Type(Type::Array(a)) = &mut schema.kind {
+        a.max_items = Some(max);
+    }
+    schema
+}
+
+impl<T: OaSchema, const CAP: usize> OaSchema for ::arrayvec::ArrayVec<T, CAP> {
+    fn schema() -> Schema {
+        with_max_items(Vec::<T>::schema(), CAP)
+    }
+
+    fn schema_ref() -> ReferenceOr<Schema> {
+        let ReferenceOr::Item(schema) = Vec::<T>::schema_ref() else {
+            unreachable!("Arrays are never references")
+        };
+        ReferenceOr::Item(with_max_items(schema, CAP))
+    }
+}
+
+impl<const CAP: usize> OaSchema for ::arrayvec::ArrayString<CAP> {
+    fn schema() -> Schema {
+        let mut schema = Schema::new_string();
+        if let SchemaKind::Type(Type::String(s)) = &mut schema.kind {
+            // CAP is in bytes, so this is an upper bound on the number of characters.
+            s.max_length = Some(CAP);
+        }
+        schema
+    }
+}
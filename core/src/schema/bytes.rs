@@ -0,0 +1,5 @@
+use crate::{impl_oa_schema, OaSchema};
+
+// Serde serializes both as a sequence of bytes, i.e. an array of integers in JSON.
+impl_oa_schema!(::bytes::Bytes, <Vec<u8> as OaSchema>::schema());
+impl_oa_schema!(::bytes::BytesMut, <Vec<u8> as OaSchema>::schema());
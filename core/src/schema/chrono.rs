@@ -3,4 +3,25 @@ use crate::impl_oa_schema;
 impl_oa_schema!(::chrono::NaiveDate, crate::Schema::new_string().with_format("date"));
 impl_oa_schema!(::chrono::DateTime<::chrono::Utc>, crate::Schema::new_string().with_format("date-time"));
 impl_oa_schema!(::chrono::DateTime<::chrono::FixedOffset>, crate::Schema::new_string().with_format("date-time"));
-impl_oa_schema!(::chrono::NaiveDateTime, crate::Schema::new_string().with_format("date-time"));
\ No newline at end of file
+impl_oa_schema!(::chrono::NaiveDateTime, crate::Schema::new_string().with_format("date-time"));
+impl_oa_schema!(::chrono::DateTime<::chrono::Local>, crate::Schema::new_string().with_format("date-time"));
+impl_oa_schema!(::chrono::NaiveTime, crate::Schema::new_string().with_format("time"));
+
+// Serialized as a `(seconds, nanoseconds)` tuple, with the nanoseconds in `0..1_000_000_000`.
+impl_oa_schema!(::chrono::Duration, {
+    use crate::{OaSchema, Schema, SchemaKind, Type};
+
+    let mut nanos = <i32 as OaSchema>::schema();
+    if let SchemaKind::Type(Type::Integer(i)) = &mut nanos.kind {
+        i.minimum = Some(0);
+        i.maximum = Some(999_999_999);
+    }
+    let items = vec![<i64 as OaSchema>::schema().into(), nanos.into()];
+    let mut schema = Schema::new_array(Schema::new_any_of(items));
+    if let SchemaKind::Type(Type::Array(a)) = &mut schema.kind {
+        a.min_items = Some(2);
+        a.max_items = Some(2);
+    }
+    schema.data.extensions.insert(crate::PREFIX_ITEMS_EXTENSION.to_string(), serde_json::Value::Bool(true));
+    schema
+});
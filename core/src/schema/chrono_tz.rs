@@ -0,0 +1,5 @@
+use crate::{impl_oa_schema, Schema};
+
+impl_oa_schema!(::chrono_tz::Tz, Schema::new_str_enum(
+    ::chrono_tz::TZ_VARIANTS.iter().map(|tz| tz.name().to_string()).collect()
+));
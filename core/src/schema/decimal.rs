@@ -0,0 +1,26 @@
+use openapiv3::{Schema, SchemaData, SchemaKind, StringType, Type, VariantOrUnknownOrEmpty};
+
+use crate::impl_oa_schema;
+
+/// Decimal types serialize as strings by default, to keep their precision. With the
+/// `decimal_as_number` feature, they're documented as numbers instead, for when they're serialized
+/// with e.g. `rust_decimal/serde-float` or `bigdecimal/serde-json`.
+fn decimal() -> Schema {
+    if cfg!(feature = "decimal_as_number") {
+        return Schema::new_number();
+    }
+    Schema {
+        data: SchemaData::default(),
+        kind: SchemaKind::Type(Type::String(StringType {
+            format: VariantOrUnknownOrEmpty::Unknown("decimal".to_string()),
+            pattern: Some(r"^-?[0-9]+(\.[0-9]+)?([eE][-+]?[0-9]+)?$".to_string()),
+            ..StringType::default()
+        })),
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl_oa_schema!(::bigdecimal::BigDecimal, decimal());
+
+#[cfg(feature = "rust_decimal")]
+impl_oa_schema!(::rust_decimal::Decimal, decimal());
@@ -0,0 +1,3 @@
+use crate::{impl_oa_schema, Schema};
+
+impl_oa_schema!(::email_address::EmailAddress, Schema::new_string().with_format("email"));
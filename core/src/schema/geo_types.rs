@@ -0,0 +1,70 @@
+use ::geo_types::{
+    Coord, CoordNum, Line, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon,
+};
+use openapiv3::Schema;
+
+use crate::OaSchema;
+
+impl<T: CoordNum + OaSchema> OaSchema for Coord<T> {
+    fn schema() -> Schema {
+        let mut o = Schema::new_object();
+        o.properties_mut().insert("x", T::schema());
+        o.properties_mut().insert("y", T::schema());
+        o.required_mut().push("x".to_string());
+        o.required_mut().push("y".to_string());
+        o
+    }
+}
+
+// Newtypes serialize as what they wrap.
+impl<T: CoordNum + OaSchema> OaSchema for Point<T> {
+    fn schema() -> Schema {
+        Coord::<T>::schema()
+    }
+}
+
+impl<T: CoordNum + OaSchema> OaSchema for LineString<T> {
+    fn schema() -> Schema {
+        Vec::<Coord<T>>::schema()
+    }
+}
+
+impl<T: CoordNum + OaSchema> OaSchema for MultiPoint<T> {
+    fn schema() -> Schema {
+        Vec::<Point<T>>::schema()
+    }
+}
+
+impl<T: CoordNum + OaSchema> OaSchema for MultiLineString<T> {
+    fn schema() -> Schema {
+        Vec::<LineString<T>>::schema()
+    }
+}
+
+impl<T: CoordNum + OaSchema> OaSchema for MultiPolygon<T> {
+    fn schema() -> Schema {
+        Vec::<Polygon<T>>::schema()
+    }
+}
+
+impl<T: CoordNum + OaSchema> OaSchema for Line<T> {
+    fn schema() -> Schema {
+        let mut o = Schema::new_object();
+        o.properties_mut().insert("start", Coord::<T>::schema());
+        o.properties_mut().insert("end", Coord::<T>::schema());
+        o.required_mut().push("start".to_string());
+        o.required_mut().push("end".to_string());
+        o
+    }
+}
+
+impl<T: CoordNum + OaSchema> OaSchema for Polygon<T> {
+    fn schema() -> Schema {
+        let mut o = Schema::new_object();
+        o.properties_mut().insert("exterior", LineString::<T>::schema());
+        o.properties_mut().insert("interiors", Vec::<LineString<T>>::schema());
+        o.required_mut().push("exterior".to_string());
+        o.required_mut().push("interiors".to_string());
+        o
+    }
+}
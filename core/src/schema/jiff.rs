@@ -0,0 +1,11 @@
+use crate::{impl_oa_schema, Schema};
+
+impl_oa_schema!(::jiff::Timestamp, Schema::new_string().with_format("date-time"));
+// RFC 9557, i.e. RFC 3339 with the time zone name in brackets.
+impl_oa_schema!(::jiff::Zoned, Schema::new_string());
+impl_oa_schema!(::jiff::civil::DateTime, Schema::new_string().with_format("date-time"));
+impl_oa_schema!(::jiff::civil::Date, Schema::new_string().with_format("date"));
+impl_oa_schema!(::jiff::civil::Time, Schema::new_string().with_format("time"));
+// ISO 8601 durations, e.g. `P1DT2H`.
+impl_oa_schema!(::jiff::Span, Schema::new_string().with_format("duration"));
+impl_oa_schema!(::jiff::SignedDuration, Schema::new_string().with_format("duration"));
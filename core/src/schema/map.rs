@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashMap};
+
+use openapiv3::{
+    AdditionalProperties, ReferenceOr, Schema, SchemaKind, StringType, Type, VariantOrUnknownOrEmpty,
+};
+
+use crate::OaSchema;
+
+/// Extension holding the schema of a map's keys. OpenAPI 3.0 has no keyword for it, so it's
+/// converted to `propertyNames` for OpenAPI 3.1 and JSON Schema.
+pub const KEY_SCHEMA_EXTENSION: &str = "x-key-schema";
+
+/// Schemas for map types that document their keys. Unlike `OaSchema`, which maps implement for
+/// any key type, these require the key to implement `OaSchema`.
+pub trait MapSchema {
+    /// The map with its keys documented in `x-key-schema`, if they're more constrained than any string.
+    fn key_schema_ref() -> ReferenceOr<Schema>;
+
+    /// The map as an object with an optional property for each key, if the keys are a string enum.
+    /// Otherwise, the same as `OaSchema::schema_ref`.
+    fn explicit_keys_schema() -> ReferenceOr<Schema>;
+}
+
+/// The schema of a map's keys, if they're more constrained than any string. Keys are always
+/// strings in JSON, so integer keys are documented as strings of digits.
+fn key_schema<K: OaSchema>() -> Option<ReferenceOr<Schema>> {
+    let schema = match K::schema_ref() {
+        ReferenceOr::Item(schema) => schema,
+        reference => return Some(reference),
+    };
+    match &schema.kind {
+        SchemaKind::Type(Type::String(s))
+            if s.format.is_empty() && s.pattern.is_none() && s.enumeration.is_empty()
+                && s.min_length.is_none() && s.max_length.is_none() => None,
+        SchemaKind::Type(Type::Integer(i)) => {
+            let pattern = if i.minimum.is_some_and(|m| m >= 0) { "^[0-9]+$" } else { "^-?[0-9]+$" };
+            Some(ReferenceOr::Item(Schema {
+                data: Default::default(),
+                kind: SchemaKind::Type(Type::String(StringType {
+                    format: VariantOrUnknownOrEmpty::Empty,
+                    pattern: Some(pattern.to_string()),
+                    ..StringType::default()
+                })),
+            }))
+        }
+        _ => Some(ReferenceOr::Item(schema)),
+    }
+}
+
+fn map<K: OaSchema>(values: ReferenceOr<Schema>) -> Schema {
+    let mut schema = Schema::new_map(values);
+    if let Some(key) = key_schema::<K>() {
+        let key = serde_json::to_value(key).expect("Serializing key schema failed.");
+        schema.data.extensions.insert(KEY_SCHEMA_EXTENSION.to_string(), key);
+    }
+    schema
+}
+
+fn explicit_keys<K: OaSchema, V: OaSchema>() -> ReferenceOr<Schema> {
+    let SchemaKind::Type(Type::String(StringType { enumeration, .. })) = K::schema().kind else {
+        return ReferenceOr::Item(map::<K>(V::schema_ref()));
+    };
+    if enumeration.is_empty() {
+        return ReferenceOr::Item(map::<K>(V::schema_ref()));
+    }
+    let mut o = Schema::new_object();
+    for key in enumeration {
+        o.properties_mut().insert(key, V::schema_ref());
+    }
+    if let SchemaKind::Type(Type::Object(obj)) = &mut o.kind {
+        obj.additional_properties = Some(AdditionalProperties::Any(false));
+    }
+    ReferenceOr::Item(o)
+}
+
+macro_rules! impl_oa_schema_map {
+    ($($t:ident $(<$extra:ident>)?),*) => {
+        $(
+            impl<K, V: OaSchema $(, $extra)?> OaSchema for $t<K, V $(, $extra)?> {
+                fn schema() -> Schema {
+                    Schema::new_map(V::schema())
+                }
+
+                fn schema_ref() -> ReferenceOr<Schema> {
+                    ReferenceOr::Item(Schema::new_map(V::schema_ref()))
+                }
+            }
+
+            impl<K: OaSchema, V: OaSchema $(, $extra)?> MapSchema for $t<K, V $(, $extra)?> {
+                fn key_schema_ref() -> ReferenceOr<Schema> {
+                    ReferenceOr::Item(map::<K>(V::schema_ref()))
+                }
+
+                fn explicit_keys_schema() -> ReferenceOr<Schema> {
+                    explicit_keys::<K, V>()
+                }
+            }
+        )*
+    };
+}
+
+impl_oa_schema_map!(HashMap<S>, BTreeMap);
+
+#[cfg(feature = "indexmap")]
+use indexmap::IndexMap;
+#[cfg(feature = "indexmap")]
+impl_oa_schema_map!(IndexMap<S>);
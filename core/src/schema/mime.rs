@@ -0,0 +1,5 @@
+use crate::{impl_oa_schema, Schema};
+
+// mime doesn't implement `Serialize`, so fields are serialized with an adapter that writes the
+// essence string, e.g. `#[serde(with = "...")]`, and documented by this impl.
+impl_oa_schema!(::mime::Mime, Schema::new_string());
@@ -0,0 +1,148 @@
+use std::num::{
+    NonZeroI8, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI128, NonZeroIsize, NonZeroU8, NonZeroU16,
+    NonZeroU32, NonZeroU64, NonZeroU128, NonZeroUsize,
+};
+
+use openapiv3::{
+    IntegerFormat, IntegerType, NumberFormat, NumberType, Schema, SchemaData, SchemaKind, StringType, Type,
+    VariantOrUnknownOrEmpty,
+};
+
+use crate::{impl_oa_schema, OaSchema};
+
+fn integer(format: IntegerFormat, minimum: Option<i64>, maximum: Option<i64>) -> Schema {
+    Schema {
+        data: SchemaData::default(),
+        kind: SchemaKind::Type(Type::Integer(IntegerType {
+            format: VariantOrUnknownOrEmpty::Item(format),
+            minimum,
+            maximum,
+            ..IntegerType::default()
+        })),
+    }
+}
+
+fn number(format: NumberFormat) -> Schema {
+    Schema {
+        data: SchemaData::default(),
+        kind: SchemaKind::Type(Type::Number(NumberType {
+            format: VariantOrUnknownOrEmpty::Item(format),
+            ..NumberType::default()
+        })),
+    }
+}
+
+/// 128-bit integers don't fit `int64`. By default they're documented as unbounded integers. With the
+/// `i128_as_string` feature, they're documented as strings of digits, for clients that can't parse
+/// large numbers (pair it with e.g. `serde_with::DisplayFromStr`). Non-zero strings can't start with `0`.
+fn integer_128(unsigned: bool, nonzero: bool) -> Schema {
+    if cfg!(feature = "i128_as_string") {
+        let pattern = match (unsigned, nonzero) {
+            (true, false) => "^[0-9]+$",
+            (false, false) => "^-?[0-9]+$",
+            (true, true) => "^[1-9][0-9]*$",
+            (false, true) => "^-?[1-9][0-9]*$",
+        };
+        return Schema {
+            data: SchemaData::default(),
+            kind: SchemaKind::Type(Type::String(StringType {
+                pattern: Some(pattern.to_string()),
+                ..StringType::default()
+            })),
+        };
+    }
+    Schema {
+        data: SchemaData::default(),
+        kind: SchemaKind::Type(Type::Integer(IntegerType {
+            minimum: unsigned.then_some(nonzero as i64),
+            ..IntegerType::default()
+        })),
+    }
+}
+
+impl_oa_schema!(i8, integer(IntegerFormat::Int32, Some(i8::MIN as i64), Some(i8::MAX as i64)));
+impl_oa_schema!(u8, integer(IntegerFormat::Int32, Some(0), Some(u8::MAX as i64)));
+impl_oa_schema!(i16, integer(IntegerFormat::Int32, Some(i16::MIN as i64), Some(i16::MAX as i64)));
+impl_oa_schema!(u16, integer(IntegerFormat::Int32, Some(0), Some(u16::MAX as i64)));
+impl_oa_schema!(i32, integer(IntegerFormat::Int32, None, None));
+impl_oa_schema!(u32, integer(IntegerFormat::Int64, Some(0), Some(u32::MAX as i64)));
+impl_oa_schema!(i64, integer(IntegerFormat::Int64, None, None));
+impl_oa_schema!(u64, integer(IntegerFormat::Int64, Some(0), None));
+impl_oa_schema!(isize, integer(IntegerFormat::Int64, None, None));
+impl_oa_schema!(usize, integer(IntegerFormat::Int64, Some(0), None));
+impl_oa_schema!(i128, integer_128(false, false));
+impl_oa_schema!(u128, integer_128(true, false));
+
+impl_oa_schema!(f32, number(NumberFormat::Float));
+impl_oa_schema!(f64, number(NumberFormat::Double));
+
+macro_rules! impl_oa_schema_nonzero_unsigned {
+    ($($t:ty => $inner:ty),*) => {
+        $(
+            impl OaSchema for $t {
+                fn schema() -> Schema {
+                    let mut schema = <$inner as OaSchema>::schema();
+                    if let SchemaKind::Type(Type::Integer(i)) = &mut schema.kind {
+                        i.minimum = Some(1);
+                    }
+                    schema
+                }
+            }
+        )*
+    };
+}
+
+impl_oa_schema_nonzero_unsigned!(
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroUsize => usize
+);
+impl_oa_schema!(NonZeroU128, integer_128(true, true));
+
+// OpenAPI can't exclude a single value from a type, so signed non-zero integers have the same
+// schema as their primitive, unless they're documented as strings.
+impl_oa_schema!(NonZeroI8, i8::schema());
+impl_oa_schema!(NonZeroI16, i16::schema());
+impl_oa_schema!(NonZeroI32, i32::schema());
+impl_oa_schema!(NonZeroI64, i64::schema());
+impl_oa_schema!(NonZeroIsize, isize::schema());
+impl_oa_schema!(NonZeroI128, integer_128(false, true));
+
+impl_oa_schema!(char, Schema {
+    data: SchemaData::default(),
+    kind: SchemaKind::Type(Type::String(StringType {
+        min_length: Some(1),
+        max_length: Some(1),
+        ..StringType::default()
+    })),
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "i128_as_string")]
+    fn test_nonzero_128_as_string() {
+        fn pattern<T: OaSchema>() -> Option<String> {
+            match T::schema().kind {
+                SchemaKind::Type(Type::String(s)) => s.pattern,
+                _ => None,
+            }
+        }
+        assert_eq!(pattern::<u128>().as_deref(), Some("^[0-9]+$"));
+        assert_eq!(pattern::<NonZeroU128>().as_deref(), Some("^[1-9][0-9]*$"));
+        assert_eq!(pattern::<NonZeroI128>().as_deref(), Some("^-?[1-9][0-9]*$"));
+    }
+
+    #[test]
+    #[cfg(not(feature = "i128_as_string"))]
+    fn test_nonzero_128() {
+        let SchemaKind::Type(Type::Integer(i)) = NonZeroU128::schema().kind else {
+            panic!("NonZeroU128 is an integer");
+        };
+        assert_eq!(i.minimum, Some(1));
+    }
+}
@@ -0,0 +1,16 @@
+use openapiv3::{SchemaData, SchemaKind, StringType, Type};
+
+use crate::{impl_oa_schema, Schema};
+
+fn version() -> Schema {
+    Schema {
+        data: SchemaData::default(),
+        kind: SchemaKind::Type(Type::String(StringType {
+            pattern: Some(r"^[0-9]+\.[0-9]+\.[0-9]+(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?$".to_string()),
+            ..StringType::default()
+        })),
+    }
+}
+
+impl_oa_schema!(::semver::Version, version());
+impl_oa_schema!(::semver::VersionReq, Schema::new_string());
@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ::serde_with::base64::{Alphabet, Base64};
+use ::serde_with::formats::{Format, Strictness};
+use ::serde_with::{
+    BoolFromInt, DisplayFromStr, DurationMicroSeconds, DurationMicroSecondsWithFrac, DurationMilliSeconds,
+    DurationMilliSecondsWithFrac, DurationNanoSeconds, DurationNanoSecondsWithFrac, DurationSeconds,
+    DurationSecondsWithFrac, NoneAsEmptyString, Same, StringWithSeparator, TimestampMicroSeconds,
+    TimestampMicroSecondsWithFrac, TimestampMilliSeconds, TimestampMilliSecondsWithFrac, TimestampNanoSeconds,
+    TimestampNanoSecondsWithFrac, TimestampSeconds, TimestampSecondsWithFrac,
+};
+use openapiv3::{IntegerType, ReferenceOr, Schema, SchemaKind, Type};
+
+use crate::schema::nullable;
+use crate::{OaSchema, OaSchemaAs};
+
+impl<T: OaSchema + ?Sized> OaSchemaAs<T> for Same {
+    fn schema() -> Schema {
+        T::schema()
+    }
+
+    fn schema_ref() -> ReferenceOr<Schema> {
+        T::schema_ref()
+    }
+}
+
+impl<T: ?Sized> OaSchemaAs<T> for DisplayFromStr {
+    fn schema() -> Schema {
+        Schema::new_string()
+    }
+}
+
+impl<T> OaSchemaAs<Option<T>> for NoneAsEmptyString {
+    fn schema() -> Schema {
+        Schema::new_string()
+    }
+}
+
+impl<T: ?Sized, A: Alphabet, P: Format> OaSchemaAs<T> for Base64<A, P> {
+    fn schema() -> Schema {
+        Schema::new_string().with_format("byte")
+    }
+}
+
+impl<S: Strictness> OaSchemaAs<bool> for BoolFromInt<S> {
+    fn schema() -> Schema {
+        Schema {
+            data: Default::default(),
+            kind: SchemaKind::Type(Type::Integer(IntegerType {
+                minimum: Some(0),
+                maximum: Some(1),
+                ..IntegerType::default()
+            })),
+        }
+    }
+}
+
+impl<Sep, T, C> OaSchemaAs<C> for StringWithSeparator<Sep, T> {
+    fn schema() -> Schema {
+        Schema::new_string()
+    }
+}
+
+/// Timestamps and durations are serialized as `FORMAT`, e.g. `i64`, `f64` or `String`.
+macro_rules! impl_oa_schema_as_format {
+    ($($t:ident),* $(,)?) => {
+        $(
+            impl<T: ?Sized, F: Format + OaSchema, S: Strictness> OaSchemaAs<T> for $t<F, S> {
+                fn schema() -> Schema {
+                    F::schema()
+                }
+            }
+        )*
+    };
+}
+
+impl_oa_schema_as_format!(
+    TimestampSeconds,
+    TimestampMilliSeconds,
+    TimestampMicroSeconds,
+    TimestampNanoSeconds,
+    TimestampSecondsWithFrac,
+    TimestampMilliSecondsWithFrac,
+    TimestampMicroSecondsWithFrac,
+    TimestampNanoSecondsWithFrac,
+    DurationSeconds,
+    DurationMilliSeconds,
+    DurationMicroSeconds,
+    DurationNanoSeconds,
+    DurationSecondsWithFrac,
+    DurationMilliSecondsWithFrac,
+    DurationMicroSecondsWithFrac,
+    DurationNanoSecondsWithFrac,
+);
+
+impl<T, U: OaSchemaAs<T>> OaSchemaAs<Vec<T>> for Vec<U> {
+    fn schema() -> Schema {
+        Schema::new_array(U::schema())
+    }
+
+    fn schema_ref() -> ReferenceOr<Schema> {
+        ReferenceOr::Item(Schema::new_array(U::schema_ref()))
+    }
+}
+
+impl<T, U: OaSchemaAs<T>> OaSchemaAs<Option<T>> for Option<U> {
+    fn schema() -> Schema {
+        let mut schema = U::schema();
+        schema.nullable = true;
+        schema
+    }
+
+    fn schema_ref() -> ReferenceOr<Schema> {
+        nullable(U::schema_ref())
+    }
+}
+
+// Keys are strings in JSON whatever their adapter, so only the values are documented.
+impl<K, V, S, KU, VU: OaSchemaAs<V>> OaSchemaAs<HashMap<K, V, S>> for HashMap<KU, VU> {
+    fn schema() -> Schema {
+        Schema::new_map(VU::schema_ref())
+    }
+}
+
+impl<K, V, KU, VU: OaSchemaAs<V>> OaSchemaAs<BTreeMap<K, V>> for BTreeMap<KU, VU> {
+    fn schema() -> Schema {
+        Schema::new_map(VU::schema_ref())
+    }
+}
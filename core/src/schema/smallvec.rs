@@ -0,0 +1,17 @@
+use openapiv3::{ReferenceOr, Schema};
+
+use crate::OaSchema;
+
+impl<A> OaSchema for ::smallvec::SmallVec<A>
+where
+    A: ::smallvec::Array,
+    A::Item: OaSchema,
+{
+    fn schema() -> Schema {
+        Vec::<A::Item>::schema()
+    }
+
+    fn schema_ref() -> ReferenceOr<Schema> {
+        Vec::<A::Item>::schema_ref()
+    }
+}
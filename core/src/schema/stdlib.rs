@@ -0,0 +1,127 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use openapiv3::{AnySchema, ReferenceOr, Schema, SchemaData, SchemaKind, Type};
+
+use crate::{impl_oa_schema, OaSchema};
+
+fn array(items: ReferenceOr<Schema>, len: Option<usize>, unique: bool) -> Schema {
+    let mut schema = Schema::new_array(items);
+    if let SchemaKind::Type(Type::Array(a)) = &mut schema.kind {
+        a.min_items = len;
+        a.max_items = len;
+        a.unique_items = unique;
+    }
+    schema
+}
+
+/// Smart pointers and references serialize as what they point to.
+macro_rules! impl_oa_schema_pointer {
+    ($($t:ty),*) => {
+        $(
+            impl<T: OaSchema + ?Sized> OaSchema for $t {
+                fn schema_ref() -> ReferenceOr<Schema> {
+                    T::schema_ref()
+                }
+
+                fn schema() -> Schema {
+                    T::schema()
+                }
+            }
+        )*
+    };
+}
+
+impl_oa_schema_pointer!(&T, Box<T>, Rc<T>, Arc<T>);
+
+impl<T: OaSchema + ToOwned + ?Sized> OaSchema for Cow<'_, T> {
+    fn schema_ref() -> ReferenceOr<Schema> {
+        T::schema_ref()
+    }
+
+    fn schema() -> Schema {
+        T::schema()
+    }
+}
+
+impl_oa_schema!(str, Schema::new_string());
+
+macro_rules! impl_oa_schema_sequence {
+    ($($t:ty => $unique:expr),*) => {
+        $(
+            impl<T: OaSchema> OaSchema for $t {
+                fn schema_ref() -> ReferenceOr<Schema> {
+                    ReferenceOr::Item(array(T::schema_ref(), None, $unique))
+                }
+
+                fn schema() -> Schema {
+                    array(ReferenceOr::Item(T::schema()), None, $unique)
+                }
+            }
+        )*
+    };
+}
+
+impl_oa_schema_sequence!([T] => false, VecDeque<T> => false, HashSet<T> => true, BTreeSet<T> => true);
+
+impl<T: OaSchema, const N: usize> OaSchema for [T; N] {
+    fn schema_ref() -> ReferenceOr<Schema> {
+        ReferenceOr::Item(array(T::schema_ref(), Some(N), false))
+    }
+
+    fn schema() -> Schema {
+        array(ReferenceOr::Item(T::schema()), Some(N), false)
+    }
+}
+
+/// Serde serializes both as an object with whole seconds and the remaining nanoseconds.
+fn seconds_and_nanos(secs: &str, nanos: &str) -> Schema {
+    let mut o = Schema::new_object();
+    o.properties_mut().insert(secs, u64::schema());
+    o.properties_mut().insert(nanos, u32::schema());
+    o.required_mut().push(secs.to_string());
+    o.required_mut().push(nanos.to_string());
+    o
+}
+
+impl_oa_schema!(Duration, seconds_and_nanos("secs", "nanos"));
+impl_oa_schema!(SystemTime, seconds_and_nanos("secs_since_epoch", "nanos_since_epoch"));
+
+impl_oa_schema!(Ipv4Addr, Schema::new_string().with_format("ipv4"));
+impl_oa_schema!(Ipv6Addr, Schema::new_string().with_format("ipv6"));
+impl_oa_schema!(IpAddr, Schema::new_one_of(vec![
+    ReferenceOr::Item(Ipv4Addr::schema()),
+    ReferenceOr::Item(Ipv6Addr::schema()),
+]));
+impl_oa_schema!(SocketAddr, Schema::new_string());
+impl_oa_schema!(SocketAddrV4, Schema::new_string());
+impl_oa_schema!(SocketAddrV6, Schema::new_string());
+
+impl_oa_schema!(PathBuf, Schema::new_string());
+impl_oa_schema!(Path, Schema::new_string());
+
+/// Serde serializes `PhantomData` as unit, i.e. `null`.
+impl<T: ?Sized> OaSchema for PhantomData<T> {
+    fn schema() -> Schema {
+        Schema {
+            data: SchemaData {
+                nullable: true,
+                ..SchemaData::default()
+            },
+            kind: SchemaKind::Any(AnySchema {
+                enumeration: vec![serde_json::Value::Null],
+                ..AnySchema::default()
+            }),
+        }
+    }
+
+    fn body_schema() -> Option<ReferenceOr<Schema>> {
+        None
+    }
+}
@@ -4,3 +4,12 @@ impl_oa_schema!(::time::OffsetDateTime, Schema::new_string().with_format("date-t
 impl_oa_schema!(::time::PrimitiveDateTime, Schema::new_string().with_format("date-time"));
 impl_oa_schema!(::time::Date, Schema::new_string().with_format("date"));
 impl_oa_schema!(::time::Time, Schema::new_string().with_format("time"));
+
+// Serialized as `<seconds>.<nanoseconds>`.
+impl_oa_schema!(::time::Duration, Schema {
+    data: crate::SchemaData::default(),
+    kind: crate::SchemaKind::Type(crate::Type::String(crate::StringType {
+        pattern: Some(r"^-?[0-9]+\.[0-9]{9}$".to_string()),
+        ..crate::StringType::default()
+    })),
+});
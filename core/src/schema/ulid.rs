@@ -0,0 +1,14 @@
+use openapiv3::{SchemaData, SchemaKind, StringType, Type, VariantOrUnknownOrEmpty};
+
+use crate::{impl_oa_schema, Schema};
+
+impl_oa_schema!(::ulid::Ulid, Schema {
+    data: SchemaData::default(),
+    kind: SchemaKind::Type(Type::String(StringType {
+        format: VariantOrUnknownOrEmpty::Unknown("ulid".to_string()),
+        pattern: Some("^[0-9A-HJKMNP-TV-Z]{26}$".to_string()),
+        min_length: Some(26),
+        max_length: Some(26),
+        ..StringType::default()
+    })),
+});
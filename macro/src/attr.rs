@@ -1,8 +1,9 @@
 use quote::ToTokens;
 use serde_derive_internals::ast::Field;
-use structmeta::StructMeta;
+use structmeta::{NameArgs, StructMeta};
 use syn::spanned::Spanned;
-use syn::LitStr;
+use syn::parse::{Parse, ParseStream};
+use syn::{LitInt, LitStr, Token};
 
 /// Available attributes on a struct
 /// For attributes that have the same name as `serde` attributes, you can use either one.
@@ -14,6 +15,30 @@ pub struct FieldAttributes {
     /// By default, oasgen will use references when possible
     /// If you want to inline the schema, use `#[oasgen(inline)]`
     pub inline: bool,
+    /// Mark the field required even if it's an `Option`, i.e. it must be present, but may be null.
+    /// To document an optional, nullable field (e.g. double-option patch semantics), use `Option<Option<T>>`.
+    pub required: bool,
+    /// For maps, document the schema of the keys in `x-key-schema`. Requires the key type to implement `OaSchema`.
+    pub key_schema: bool,
+    /// For maps keyed by a string enum, document an optional property for each key,
+    /// instead of `additionalProperties`.
+    pub explicit_keys: bool,
+    /// Path to a `fn() -> Schema` that builds the field's schema, for foreign types without `OaSchema`.
+    pub schema_with: Option<LitStr>,
+    /// Document the field as this JSON type, e.g. `#[oasgen(type = "string", format = "binary")]`.
+    #[struct_meta(name = "type")]
+    pub ty: Option<LitStr>,
+    /// Format for the type given with `type`.
+    pub format: Option<LitStr>,
+    /// Document the field as if it were this type, e.g. `#[oasgen(as = String)]`.
+    #[struct_meta(name = "as")]
+    pub as_: Option<syn::Type>,
+    /// Title of the property.
+    pub title: Option<LitStr>,
+    /// Link to documentation of the property, e.g. `external_docs(url = "https://...")`.
+    pub external_docs: Option<NameArgs<ExternalDocs>>,
+    /// Vendor extensions of the property, e.g. `extension("x-go-name" = "ID")`.
+    pub extension: Option<Vec<Extension>>,
 }
 
 impl FieldAttributes {
@@ -24,9 +49,39 @@ impl FieldAttributes {
         if other.inline {
             self.inline = true;
         }
+        if other.required {
+            self.required = true;
+        }
+        if other.key_schema {
+            self.key_schema = true;
+        }
+        if other.explicit_keys {
+            self.explicit_keys = true;
+        }
         if other.skip_serializing_if.is_some() {
             self.skip_serializing_if = other.skip_serializing_if.clone();
         }
+        if other.schema_with.is_some() {
+            self.schema_with = other.schema_with.clone();
+        }
+        if other.ty.is_some() {
+            self.ty = other.ty.clone();
+        }
+        if other.format.is_some() {
+            self.format = other.format.clone();
+        }
+        if other.as_.is_some() {
+            self.as_ = other.as_.clone();
+        }
+        if other.title.is_some() {
+            self.title = other.title.clone();
+        }
+        if other.external_docs.is_some() {
+            self.external_docs = other.external_docs.clone();
+        }
+        if let Some(extension) = &other.extension {
+            self.extension.get_or_insert_default().extend(extension.iter().cloned());
+        }
     }
 
     pub fn merge_serde(&mut self, other: &Field) {
@@ -47,7 +102,7 @@ impl TryFrom<&Vec<syn::Attribute>> for FieldAttributes {
 
     fn try_from(attrs: &Vec<syn::Attribute>) -> Result<Self, Self::Error> {
         let attrs = attrs
-            .into_iter()
+            .iter()
             .filter(|a| a.path().get_ident().map(|i| i == "oasgen").unwrap_or(false))
             .map(|a| a.parse_args())
             .collect::<Result<Vec<FieldAttributes>, syn::Error>>()?;
@@ -60,6 +115,88 @@ impl TryFrom<&Vec<syn::Attribute>> for FieldAttributes {
     }
 }
 
+/// Available attributes on a struct or enum definition.
+#[derive(StructMeta, Default)]
+pub struct ContainerAttributes {
+    /// Component name for the schema. Takes precedence over `#[serde(rename)]`.
+    pub rename: Option<LitStr>,
+    /// Prefix the component name with the module path, e.g. `my_crate.api.User`.
+    /// Use this to give two types with the same name distinct, stable component names.
+    pub qualified_name: bool,
+    /// Also register the type in `components.responses`, as a JSON response with the
+    /// docstring as its description.
+    pub response: bool,
+    /// Also register the type in `components.requestBodies`, as a required JSON body.
+    pub request_body: bool,
+    /// Names of headers registered with `register_header!`, added to the response registered
+    /// with `#[oasgen(response)]`, e.g. `headers("X-Request-Id")`.
+    pub headers: Option<Vec<LitStr>>,
+    /// Document a fieldless enum as an integer enum of its discriminants, for enums serialized as
+    /// integers, e.g. with `serde_repr`.
+    pub repr: bool,
+    /// Where clause for the `OaSchema` impl of a generic type, replacing the default `T: OaSchema` bounds.
+    pub bound: Option<LitStr>,
+    /// Title of the schema.
+    pub title: Option<LitStr>,
+    /// Link to documentation of the schema, e.g. `external_docs(url = "https://...", description = "...")`.
+    pub external_docs: Option<NameArgs<ExternalDocs>>,
+    /// Vendor extensions of the schema, e.g. `extension("x-internal" = true)`.
+    pub extension: Option<Vec<Extension>>,
+}
+
+impl ContainerAttributes {
+    pub fn merge_with(&mut self, other: &Self) {
+        if other.rename.is_some() {
+            self.rename = other.rename.clone();
+        }
+        if other.qualified_name {
+            self.qualified_name = true;
+        }
+        if other.response {
+            self.response = true;
+        }
+        if other.request_body {
+            self.request_body = true;
+        }
+        if let Some(headers) = &other.headers {
+            self.headers.get_or_insert_default().extend(headers.iter().cloned());
+        }
+        if other.repr {
+            self.repr = true;
+        }
+        if other.bound.is_some() {
+            self.bound = other.bound.clone();
+        }
+        if other.title.is_some() {
+            self.title = other.title.clone();
+        }
+        if other.external_docs.is_some() {
+            self.external_docs = other.external_docs.clone();
+        }
+        if let Some(extension) = &other.extension {
+            self.extension.get_or_insert_default().extend(extension.iter().cloned());
+        }
+    }
+}
+
+impl TryFrom<&Vec<syn::Attribute>> for ContainerAttributes {
+    type Error = syn::Error;
+
+    fn try_from(attrs: &Vec<syn::Attribute>) -> Result<Self, Self::Error> {
+        let attrs = attrs
+            .iter()
+            .filter(|a| a.path().get_ident().map(|i| i == "oasgen").unwrap_or(false))
+            .map(|a| a.parse_args())
+            .collect::<Result<Vec<ContainerAttributes>, syn::Error>>()?;
+        let mut attrs = attrs.into_iter();
+        let mut result = attrs.next().unwrap_or_default();
+        for attr in attrs {
+            result.merge_with(&attr);
+        }
+        Ok(result)
+    }
+}
+
 /// available parameters for #[oasgen] attribute.
 #[derive(StructMeta, Default)]
 pub struct OperationAttributes {
@@ -68,9 +205,114 @@ pub struct OperationAttributes {
     pub tags: Option<Vec<LitStr>>,
     pub operation_id: Option<LitStr>,
     pub deprecated: bool,
+    /// Names of parameters registered with `register_parameter!`, e.g. `parameters("Page")`.
+    pub parameters: Option<Vec<LitStr>>,
+    /// Responses registered with `register_response!` or `#[oasgen(response)]`,
+    /// e.g. `responses(404 = "NotFound")`.
+    pub responses: Option<Vec<ResponseRef>>,
+    /// Request body registered with `register_request_body!` or `#[oasgen(request_body)]`.
+    /// Replaces the body derived from the handler arguments.
+    pub request_body: Option<LitStr>,
+    /// Names of headers registered with `register_header!`, added to the responses derived from the
+    /// handler's return type, e.g. `headers("X-Request-Id")`.
+    pub headers: Option<Vec<LitStr>>,
+    /// Link to documentation of the operation, e.g. `external_docs(url = "https://...")`.
+    pub external_docs: Option<NameArgs<ExternalDocs>>,
+    /// Vendor extensions of the operation, e.g. `extension("x-internal" = true)`.
+    pub extension: Option<Vec<Extension>>,
+}
+
+/// `<status> = "<name>"`, referencing `#/components/responses/<name>`.
+#[derive(Clone)]
+pub struct ResponseRef {
+    pub status: LitInt,
+    pub name: LitStr,
+}
+
+impl Parse for ResponseRef {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let status = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let name = input.parse()?;
+        Ok(Self { status, name })
+    }
+}
+
+/// `url = "...", description = "..."` of an `external_docs(...)` attribute.
+#[derive(StructMeta, Clone)]
+pub struct ExternalDocs {
+    pub url: LitStr,
+    pub description: Option<LitStr>,
+}
+
+/// `"<name>" = <value>`, where the name starts with `x-` and the value is any `serde_json::json!` value,
+/// e.g. `"x-internal" = true` or `"x-tags" = ["a", "b"]`.
+#[derive(Clone)]
+pub struct Extension {
+    pub name: LitStr,
+    pub value: proc_macro2::TokenStream,
+}
+
+impl Parse for Extension {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: LitStr = input.parse()?;
+        if !name.value().starts_with("x-") {
+            return Err(syn::Error::new(name.span(), "Extension names must start with `x-`"));
+        }
+        input.parse::<Token![=]>()?;
+        let mut value = proc_macro2::TokenStream::new();
+        while !input.is_empty() && !input.peek(Token![,]) {
+            value.extend([input.parse::<proc_macro2::TokenTree>()?]);
+        }
+        if value.is_empty() {
+            return Err(input.error("Expected an extension value"));
+        }
+        Ok(Self { name, value })
+    }
 }
 
 impl OperationAttributes {
+    /// Merge a later `#[oasgen(...)]` attribute of the same handler.
+    pub fn merge_with(&mut self, other: Self) {
+        fn extend<T>(list: &mut Option<Vec<T>>, other: Option<Vec<T>>) {
+            if let Some(other) = other {
+                list.get_or_insert_default().extend(other);
+            }
+        }
+        self.summary = other.summary.or(self.summary.take());
+        self.description = other.description.or(self.description.take());
+        self.operation_id = other.operation_id.or(self.operation_id.take());
+        self.request_body = other.request_body.or(self.request_body.take());
+        self.external_docs = other.external_docs.or(self.external_docs.take());
+        self.deprecated |= other.deprecated;
+        extend(&mut self.tags, other.tags);
+        extend(&mut self.parameters, other.parameters);
+        extend(&mut self.responses, other.responses);
+        extend(&mut self.headers, other.headers);
+        extend(&mut self.extension, other.extension);
+    }
+
+    /// Apply the attributes of the `#[oasgen]` impl block to one of its handlers. The handler's own attributes take
+    /// precedence, and lists are appended to the impl block's. The `operation_id` is unique to each handler.
+    pub fn merge_defaults(&mut self, defaults: &Self) {
+        fn prepend<T: Clone>(list: &mut Option<Vec<T>>, defaults: &Option<Vec<T>>) {
+            if let Some(defaults) = defaults {
+                let list = list.get_or_insert_default();
+                list.splice(0..0, defaults.iter().cloned());
+            }
+        }
+        self.summary = self.summary.take().or_else(|| defaults.summary.clone());
+        self.description = self.description.take().or_else(|| defaults.description.clone());
+        self.request_body = self.request_body.take().or_else(|| defaults.request_body.clone());
+        self.external_docs = self.external_docs.take().or_else(|| defaults.external_docs.clone());
+        self.deprecated |= defaults.deprecated;
+        prepend(&mut self.tags, &defaults.tags);
+        prepend(&mut self.parameters, &defaults.parameters);
+        prepend(&mut self.responses, &defaults.responses);
+        prepend(&mut self.headers, &defaults.headers);
+        prepend(&mut self.extension, &defaults.extension);
+    }
+
     pub fn merge_attributes(&mut self, attrs: &[syn::Attribute]) {
         let docstring = get_docstring(attrs).expect("Failed to parse docstring");
         if let Some(docstring) = docstring {
@@ -79,6 +321,25 @@ impl OperationAttributes {
     }
 }
 
+/// The integer type of a `#[repr(...)]` attribute, if any.
+pub(crate) fn get_repr(attrs: &[syn::Attribute]) -> Option<syn::Ident> {
+    const INTEGERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+    let mut repr = None;
+    for attr in attrs.iter().filter(|a| a.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident()
+                && INTEGERS.contains(&ident.to_string().as_str())
+            {
+                repr = Some(ident.clone());
+            }
+            Ok(())
+        });
+    }
+    repr
+}
+
 pub(crate) fn get_docstring(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
     let string_literals = attrs
         .iter()
@@ -1,15 +1,19 @@
 #![allow(non_snake_case)]
 
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use serde_derive_internals::{
     ast::{Container, Data, Style},
     Ctxt, Derive,
 };
+use syn::visit_mut::{self, VisitMut};
 use syn::{PathArguments, GenericArgument, TypePath, Type, ReturnType, FnArg, parse_macro_input, DeriveInput};
-use util::{derive_oaschema_enum, derive_oaschema_struct};
-use crate::attr::{get_docstring, OperationAttributes};
-use crate::util::derive_oaschema_newtype;
+use util::{derive_oaschema_enum, derive_oaschema_repr_enum, derive_oaschema_struct, derive_oaschema_tuple};
+use crate::attr::{get_docstring, get_repr, ContainerAttributes, OperationAttributes};
+use crate::util::{
+    derive_components, derive_oaschema_newtype, external_docs, insert_extensions, is_generic, oaschema_generics,
+    schema_data, ComponentName,
+};
 
 mod util;
 mod attr;
@@ -27,46 +31,308 @@ pub fn derive_oaschema(item: TokenStream) -> TokenStream {
 
     let id = &cont.ident;
     let docstring = get_docstring(&ast.attrs).expect("Failed to parse docstring");
-    match &cont.data {
+    let attrs = ContainerAttributes::try_from(&ast.attrs).expect("Failed to parse oasgen attributes");
+    // A `#[serde(remote = "...")]` definition documents the remote type, so it's named after it.
+    let remote = cont.attrs.remote().and_then(|path| path.segments.last()).map(|s| s.ident.to_string());
+    let name = attrs.rename
+        .as_ref()
+        .map(|s| s.value())
+        .or(remote.filter(|_| *id == cont.attrs.name().deserialize_name()))
+        .unwrap_or_else(|| cont.attrs.name().deserialize_name().to_string());
+    let name = ComponentName::new(name, attrs.qualified_name);
+    let generics = oaschema_generics(
+        cont.generics,
+        attrs.bound.as_ref(),
+        cont.attrs.de_bound().or(cont.attrs.ser_bound()),
+    );
+    if is_generic(&generics) && (attrs.response || attrs.request_body) {
+        panic!("#[oasgen(response)] and #[oasgen(request_body)] can not be used on generic types");
+    }
+    if attrs.headers.is_some() && !attrs.response {
+        panic!("#[oasgen(headers(...))] on a type requires #[oasgen(response)]");
+    }
+    let components = derive_components(id, &name, &attrs, docstring.as_deref());
+    // Fields serialized `#[serde(with = "...")]` find the definition through the remote type.
+    let remote_register = match cont.attrs.remote() {
+        Some(remote) if !is_generic(&generics) => quote! {
+            ::oasgen::__private::inventory::submit!(::oasgen::__private::RemoteRegister {
+                type_name: ::std::any::type_name::<#remote>,
+                schema: <#id as ::oasgen::OaSchema>::schema,
+                schema_ref: <#id as ::oasgen::OaSchema>::schema_ref,
+            });
+        },
+        _ => quote! {},
+    };
+    let deny_unknown_fields = cont.attrs.deny_unknown_fields();
+    let data = schema_data(
+        docstring.as_deref(),
+        attrs.title.as_ref(),
+        attrs.external_docs.as_ref().map(|d| &d.args),
+        attrs.extension.as_ref(),
+    );
+    let schema: proc_macro2::TokenStream = match &cont.data {
         Data::Struct(Style::Struct, fields) => {
-            derive_oaschema_struct(id, fields, docstring)
+            derive_oaschema_struct(id, &generics, &name, fields, data, deny_unknown_fields)
         }
         Data::Struct(Style::Newtype, fields) => {
-            derive_oaschema_newtype(id, fields.first().unwrap())
+            if attrs.title.is_some() || attrs.external_docs.is_some() || attrs.extension.is_some() {
+                panic!("#[oasgen(title, external_docs, extension)] can not be used on newtype structs, which are documented as their field");
+            }
+            derive_oaschema_newtype(id, &generics, fields.first().unwrap())
+        }
+        Data::Enum(variants) if is_repr_enum(&ast, &attrs) => {
+            let repr = get_repr(&ast.attrs);
+            derive_oaschema_repr_enum(id, &name, variants, repr, data)
         }
         Data::Enum(variants) => {
-            derive_oaschema_enum(id, variants, &cont.attrs.tag(), docstring)
+            derive_oaschema_enum(id, &generics, &name, variants, cont.attrs.tag(), data, deny_unknown_fields)
         }
-        Data::Struct(Style::Tuple | Style::Unit, _) => {
-            panic!("#[derive(OaSchema)] can not be used on tuple structs")
+        Data::Struct(Style::Tuple, fields) => {
+            derive_oaschema_tuple(id, &generics, &name, fields, data)
         }
-    }
+        Data::Struct(Style::Unit, _) => {
+            panic!("#[derive(OaSchema)] can not be used on unit structs")
+        }
+    }.into();
+    quote! {
+        #schema
+        #components
+        #remote_register
+    }.into()
+}
+
+/// Whether the enum is serialized as its integer discriminant, opted in with `#[oasgen(repr)]`.
+/// A `#[repr]` alone doesn't change how serde serializes the enum, and `serde_repr`'s derives
+/// aren't visible to this one.
+fn is_repr_enum(ast: &DeriveInput, attrs: &ContainerAttributes) -> bool {
+    let syn::Data::Enum(data) = &ast.data else {
+        return false;
+    };
+    attrs.repr && data.variants.iter().all(|v| v.fields.is_empty())
 }
 
 
 #[proc_macro_attribute]
 pub fn oasgen(attr: TokenStream, input: TokenStream) -> TokenStream {
-    let ast = parse_macro_input!(input as syn::ItemFn);
     let mut attr = syn::parse::<OperationAttributes>(attr).expect("Failed to parse operation attributes");
-    attr.merge_attributes(&ast.attrs);
-    let args = ast.sig.inputs.iter().map(|arg| {
+    match parse_macro_input!(input as syn::Item) {
+        syn::Item::Fn(mut ast) => {
+            if ast.sig.receiver().is_some() {
+                panic!("Methods taking `self` must be registered with #[oasgen] on their impl block");
+            }
+            attr.merge_attributes(&ast.attrs);
+            document_handler(&attr, &mut ast.sig, &mut ast.block, None, None);
+            quote! {
+                #ast
+            }.into()
+        }
+        syn::Item::Impl(ast) => oasgen_impl(attr, ast),
+        _ => panic!("#[oasgen] can only be used on functions and impl blocks"),
+    }
+}
+
+/// `#[oasgen]` on an impl block registers its handlers: each fn with its own `#[oasgen]` or `#[oasgen(...)]`.
+/// Attributes of the impl block apply to every handler, and `Self` in a handler's types refers to the impl's type.
+fn oasgen_impl(attr: OperationAttributes, mut ast: syn::ItemImpl) -> TokenStream {
+    if ast.trait_.is_some() {
+        panic!("#[oasgen] can not be used on trait impls");
+    }
+    if !ast.generics.params.is_empty() {
+        panic!("#[oasgen] can not be used on generic impl blocks");
+    }
+    let self_ty = ast.self_ty.clone();
+    let self_name = match self_ty.as_ref() {
+        Type::Path(TypePath { qself: None, path }) => path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }.expect("#[oasgen] can only be used on impl blocks of a named type");
+    for item in ast.items.iter_mut() {
+        let syn::ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let (method_attrs, attrs) = std::mem::take(&mut method.attrs)
+            .into_iter()
+            .partition::<Vec<_>, _>(|a| a.path().is_ident("oasgen"));
+        method.attrs = attrs;
+        if method_attrs.is_empty() {
+            continue;
+        }
+        let mut method_attr = OperationAttributes::default();
+        for a in &method_attrs {
+            if let syn::Meta::List(_) = a.meta {
+                let other = a.parse_args::<OperationAttributes>().expect("Failed to parse operation attributes");
+                method_attr.merge_with(other);
+            }
+        }
+        method_attr.merge_defaults(&attr);
+        method_attr.merge_attributes(&method.attrs);
+        let name = method.sig.ident.to_string();
+        document_handler(
+            &method_attr,
+            &mut method.sig,
+            &mut method.block,
+            Some(quote! { concat!(module_path!(), "::", #self_name, "::", #name) }),
+            Some(&self_ty),
+        );
+    }
+    quote! {
+        #ast
+    }.into()
+}
+
+/// Rewrite a handler to return its output tagged with its operation, as `::oasgen::Documented`, so the server
+/// finds the operation through the handler's type. `name` is the path of the handler, used for the default
+/// `operationId`. Without it, the server takes the path from the handler's type instead.
+///
+/// The operation is a type local to the handler, so the handler stays a single fn, whether it's free or
+/// associated. Its types can't refer to `Self`, unless `self_ty` is known, i.e. on an `#[oasgen]` impl block.
+fn document_handler(
+    attr: &OperationAttributes,
+    sig: &mut syn::Signature,
+    block: &mut syn::Block,
+    name: Option<proc_macro2::TokenStream>,
+    self_ty: Option<&Type>,
+) {
+    let ret = match &sig.output {
+        ReturnType::Default => syn::parse_quote! { () },
+        ReturnType::Type(_, ty) => ty.as_ref().clone(),
+    };
+    let operation = operation(attr, sig, &ret, name, self_ty);
+    let (_, ty_generics, _) = sig.generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish().to_token_stream();
+
+    // Bind the arguments in the body, like an `async fn` does, so they're moved into its future.
+    let mut bindings = Vec::new();
+    for (i, arg) in sig.inputs.iter_mut().enumerate() {
+        let FnArg::Typed(arg) = arg else {
+            continue;
+        };
+        match arg.pat.as_mut() {
+            syn::Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                let mutability = pat.mutability.take();
+                let ident = &pat.ident;
+                bindings.push(quote! { let #mutability #ident = #ident; });
+            }
+            pat => {
+                let ident = quote::format_ident!("__oasgen_arg{}", i);
+                bindings.push(quote! { let #pat = #ident; });
+                *pat = syn::parse_quote! { #ident };
+            }
+        }
+    }
+
+    let body = &block.stmts;
+    let (output, body) = if sig.asyncness.take().is_some() {
+        let captures = name_elided_lifetimes(sig);
+        let output_ty = if contains_impl_trait(&ret) { quote! { _ } } else { quote! { #ret } };
+        (
+            quote! { impl ::core::future::Future<Output = #ret> #captures },
+            quote! {
+                ::oasgen::__private::documented::<_, #output_ty, _>(__OasgenOperation #turbofish ::new(), async move {
+                    #(#bindings)*
+                    #(#body)*
+                })
+            },
+        )
+    } else {
+        let closure_ret = (!contains_impl_trait(&ret)).then(|| quote! { -> #ret });
+        (
+            quote! { #ret },
+            quote! {
+                ::oasgen::Documented::new(__OasgenOperation #turbofish ::new(), (move || #closure_ret {
+                    #(#bindings)*
+                    #(#body)*
+                })())
+            },
+        )
+    };
+    sig.output = syn::parse_quote! { -> ::oasgen::Documented<impl ::oasgen::OaOperation, #output> };
+    *block = syn::parse_quote! {{
+        #operation
+        #body
+    }};
+}
+
+/// The `__OasgenOperation` type, implementing `OaOperation` for the handler. It has the generics of the handler,
+/// since the operation depends on its argument types.
+fn operation(
+    attr: &OperationAttributes,
+    sig: &syn::Signature,
+    ret: &Type,
+    name: Option<proc_macro2::TokenStream>,
+    self_ty: Option<&Type>,
+) -> proc_macro2::TokenStream {
+    let resolve_self = |ty: &Type| {
+        let mut ty = ty.clone();
+        match self_ty {
+            Some(self_ty) => ReplaceSelf(self_ty).visit_type_mut(&mut ty),
+            None if mentions_self(&ty) => {
+                panic!("Handlers with `Self` in their signature must be registered with #[oasgen] on their impl block")
+            }
+            None => {}
+        }
+        ty
+    };
+    // The receiver isn't part of the request.
+    let args = sig.inputs.iter().filter_map(|arg| {
         match arg {
-            FnArg::Receiver(_) => panic!("Receiver arguments are not supported"),
-            FnArg::Typed(pat) => turbofish(pat.ty.as_ref().clone()),
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(pat) => Some(turbofish(resolve_self(&pat.ty))),
         }
     }).collect::<Vec<_>>();
-    let ret = match &ast.sig.output {
+    let ret = match &sig.output {
         ReturnType::Default => None,
-        ReturnType::Type(_, ty) => Some(turbofish(ty.as_ref().clone())),
+        ReturnType::Type(..) => Some(turbofish(resolve_self(ret))),
     };
-    let body = args.last().map(|t| {
-        quote! {
-            let body = <#t as ::oasgen::OaParameter>::body_schema();
-            if body.is_some() {
-                op.add_request_body_json(body);
+    let mut generics = sig.generics.clone();
+    if let Some(where_clause) = &mut generics.where_clause {
+        for predicate in where_clause.predicates.iter_mut() {
+            match self_ty {
+                Some(self_ty) => ReplaceSelf(self_ty).visit_where_predicate_mut(predicate),
+                None if mentions_self(predicate) => {
+                    panic!("Handlers with `Self` in their signature must be registered with #[oasgen] on their impl block")
+                }
+                None => {}
             }
         }
-    }).unwrap_or_default();
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let mut bare = generics.clone();
+    bare.where_clause = None;
+    for param in bare.params.iter_mut() {
+        match param {
+            syn::GenericParam::Lifetime(l) => l.bounds.clear(),
+            syn::GenericParam::Type(t) => t.bounds.clear(),
+            syn::GenericParam::Const(_) => {}
+        }
+    }
+    let (bare_generics, _, _) = bare.split_for_impl();
+    let phantom = generics.params.iter().filter_map(|param| {
+        match param {
+            syn::GenericParam::Lifetime(l) => {
+                let lifetime = &l.lifetime;
+                Some(quote! { &#lifetime () })
+            }
+            syn::GenericParam::Type(t) => {
+                let ident = &t.ident;
+                Some(quote! { *const #ident })
+            }
+            syn::GenericParam::Const(_) => None,
+        }
+    }).collect::<Vec<_>>();
+
+    let body = match &attr.request_body {
+        Some(name) => quote! {
+            op.request_body = Some(::oasgen::RefOr::ref_(&format!("#/components/requestBodies/{}", #name)));
+        },
+        None => args.last().map(|t| {
+            quote! {
+                let body = <#t as ::oasgen::OaParameter>::body_schema();
+                if body.is_some() {
+                    op.add_request_body_json(body);
+                }
+            }
+        }).unwrap_or_default(),
+    };
     let description = attr.description.as_ref().map(|s| s.value()).map(|c| {
         quote! {
             op.description = Some(#c.to_string());
@@ -80,6 +346,31 @@ pub fn oasgen(attr: TokenStream, input: TokenStream) -> TokenStream {
             }
         }
     }).unwrap_or_default();
+    let parameter_refs = attr.parameters.iter().flatten().map(|name| {
+        quote! {
+            op.parameters.push(::oasgen::RefOr::ref_(&format!("#/components/parameters/{}", #name)));
+        }
+    }).collect::<Vec<_>>();
+    let header_refs = attr.headers.iter().flatten().map(|name| {
+        quote! {
+            for response in op.responses.responses.values_mut().filter_map(|r| r.as_mut()) {
+                response.headers.insert(
+                    #name.to_string(),
+                    ::oasgen::RefOr::ref_(&format!("#/components/headers/{}", #name)),
+                );
+            }
+        }
+    }).collect::<Vec<_>>();
+    let response_refs = attr.responses.iter().flatten().map(|r| {
+        let status = &r.status;
+        let name = &r.name;
+        quote! {
+            op.responses.responses.insert(
+                ::oasgen::StatusCode::Code(#status),
+                ::oasgen::RefOr::ref_(&format!("#/components/responses/{}", #name)),
+            );
+        }
+    }).collect::<Vec<_>>();
     let tags = attr.tags.iter().flatten().map(|s| {
         quote! {
             op.tags.push(#s.to_string());
@@ -90,70 +381,179 @@ pub fn oasgen(attr: TokenStream, input: TokenStream) -> TokenStream {
             op.summary = Some(#c.to_string());
         }
     }).unwrap_or_default();
-    let name = ast.sig.ident.to_string();
+    let external_docs = attr.external_docs.as_ref().map(|d| {
+        let docs = external_docs(&d.args);
+        quote! {
+            op.external_docs = Some(#docs);
+        }
+    }).unwrap_or_default();
+    let extensions = insert_extensions(quote! { op.extensions }, attr.extension.as_deref().unwrap_or_default());
     let deprecated = attr.deprecated;
-    let operation_id = if let Some(id) = attr.operation_id {
+    let operation_id = if let Some(id) = &attr.operation_id {
         let id = id.value();
         quote! {
             Some(#id.to_string())
         }
-    } else {
+    } else if let Some(name) = name {
         quote! {
-            ::oasgen::__private::fn_path_to_op_id(concat!(module_path!(), "::", #name))
+            ::oasgen::__private::fn_path_to_op_id(#name)
         }
-    };
-    let submit = quote! {
-        ::oasgen::register_operation!(concat!(module_path!(), "::", #name), || {
-            let parameters: Vec<Vec<::oasgen::RefOr<::oasgen::Parameter>>> = vec![
-                #( <#args as ::oasgen::OaParameter>::parameters(), )*
-            ];
-            let parameters = parameters
-                .into_iter()
-                .flatten()
-                .collect::<Vec<::oasgen::RefOr<::oasgen::Parameter>>>();
-            let mut op = ::oasgen::Operation::default();
-            op.operation_id = #operation_id;
-            op.parameters = parameters;
-            op.deprecated = #deprecated;
-            #body
-            #ret
-            #description
-            #summary
-            #(#tags)*
-            op
-        });
+    } else {
+        quote! { None }
     };
     quote! {
-        #ast
-        #submit
-    }.into()
+        struct __OasgenOperation #bare_generics (::core::marker::PhantomData<(#(#phantom,)*)>);
+
+        impl #bare_generics __OasgenOperation #ty_generics {
+            fn new() -> Self {
+                __OasgenOperation(::core::marker::PhantomData)
+            }
+        }
+
+        impl #impl_generics ::oasgen::OaOperation for __OasgenOperation #ty_generics #where_clause {
+            fn operation() -> ::oasgen::Operation {
+                let parameters: Vec<Vec<::oasgen::RefOr<::oasgen::Parameter>>> = vec![
+                    #( <#args as ::oasgen::OaParameter>::parameters(), )*
+                ];
+                let parameters = parameters
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<::oasgen::RefOr<::oasgen::Parameter>>>();
+                let mut op = ::oasgen::Operation::default();
+                op.operation_id = #operation_id;
+                op.parameters = parameters;
+                #(#parameter_refs)*
+                op.deprecated = #deprecated;
+                #body
+                #ret
+                #(#header_refs)*
+                #(#response_refs)*
+                #description
+                #summary
+                #(#tags)*
+                #external_docs
+                #extensions
+                op
+            }
+        }
+    }
+}
+
+/// Name the elided lifetimes of the receiver and arguments, and return the `use<..>` bound capturing them (and the
+/// generics) in the handler's future, as an `async fn` does. Without it, editions before 2024 don't capture
+/// lifetimes in return position `impl Trait`.
+fn name_elided_lifetimes(sig: &mut syn::Signature) -> proc_macro2::TokenStream {
+    let mut namer = NameElidedLifetimes::default();
+    for arg in sig.inputs.iter_mut() {
+        match arg {
+            FnArg::Receiver(receiver) => {
+                if let Some((_, lifetime @ None)) = &mut receiver.reference {
+                    *lifetime = Some(namer.next());
+                    let lifetime = lifetime.clone();
+                    let mutability = receiver.mutability;
+                    *receiver.ty = syn::parse_quote! { &#lifetime #mutability Self };
+                }
+            }
+            // `impl Trait` arguments are anonymous type parameters, which can't be listed in `use<..>`.
+            FnArg::Typed(pat) if contains_impl_trait(&pat.ty) => return quote! {},
+            FnArg::Typed(pat) => namer.visit_type_mut(&mut pat.ty),
+        }
+    }
+    for lifetime in namer.lifetimes.into_iter().rev() {
+        sig.generics.params.insert(0, syn::GenericParam::Lifetime(syn::LifetimeParam::new(lifetime)));
+    }
+    if sig.generics.lifetimes().next().is_none() {
+        return quote! {};
+    }
+    let (_, captures, _) = sig.generics.split_for_impl();
+    quote! { + use #captures }
+}
+
+#[derive(Default)]
+struct NameElidedLifetimes {
+    lifetimes: Vec<syn::Lifetime>,
+}
+
+impl NameElidedLifetimes {
+    fn next(&mut self) -> syn::Lifetime {
+        let name = format!("'__oasgen{}", self.lifetimes.len());
+        let lifetime = syn::Lifetime::new(&name, proc_macro2::Span::call_site());
+        self.lifetimes.push(lifetime.clone());
+        lifetime
+    }
+}
+
+impl VisitMut for NameElidedLifetimes {
+    fn visit_type_reference_mut(&mut self, reference: &mut syn::TypeReference) {
+        if reference.lifetime.is_none() {
+            reference.lifetime = Some(self.next());
+        }
+        visit_mut::visit_type_reference_mut(self, reference);
+    }
+
+    fn visit_lifetime_mut(&mut self, lifetime: &mut syn::Lifetime) {
+        if lifetime.ident == "_" {
+            *lifetime = self.next();
+        }
+    }
+
+    // Lifetimes elided in these are higher-ranked, not parameters of the handler.
+    fn visit_type_bare_fn_mut(&mut self, _: &mut syn::TypeBareFn) {}
+
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut syn::ParenthesizedGenericArguments) {}
+}
+
+struct ReplaceSelf<'a>(&'a Type);
+
+impl VisitMut for ReplaceSelf<'_> {
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        match ty {
+            Type::Path(TypePath { qself: None, path }) if path.is_ident("Self") => *ty = self.0.clone(),
+            _ => visit_mut::visit_type_mut(self, ty),
+        }
+    }
+}
+
+fn mentions_self<T: ToTokens>(tokens: &T) -> bool {
+    fn inner(tokens: proc_macro2::TokenStream) -> bool {
+        tokens.into_iter().any(|t| match t {
+            proc_macro2::TokenTree::Ident(ident) => ident == "Self",
+            proc_macro2::TokenTree::Group(group) => inner(group.stream()),
+            _ => false,
+        })
+    }
+    inner(tokens.to_token_stream())
+}
+
+fn contains_impl_trait(ty: &Type) -> bool {
+    struct Visitor(bool);
+    impl VisitMut for Visitor {
+        fn visit_type_impl_trait_mut(&mut self, _: &mut syn::TypeImplTrait) {
+            self.0 = true;
+        }
+    }
+    let mut visitor = Visitor(false);
+    visitor.visit_type_mut(&mut ty.clone());
+    visitor.0
 }
 
 /// insert the turbofish :: into a syn::Type
 /// example: axum::Json<User> becomes axum::Json::<User>
 fn turbofish(mut ty: Type) -> Type {
     fn inner(ty: &mut Type) {
-        match ty {
-            Type::Path(TypePath { path, .. }) => {
-                let Some(last) = path.segments.last_mut() else {
-                    return;
-                };
-                match &mut last.arguments {
-                    PathArguments::AngleBracketed(args) => {
-                        args.colon2_token = Some(Default::default());
-                        for arg in args.args.iter_mut() {
-                            match arg {
-                                GenericArgument::Type(ty) => {
-                                    inner(ty);
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                    _ => {}
+        let Type::Path(TypePath { path, .. }) = ty else {
+            return;
+        };
+        let Some(last) = path.segments.last_mut() else {
+            return;
+        };
+        if let PathArguments::AngleBracketed(args) = &mut last.arguments {
+            args.colon2_token = Some(Default::default());
+            for arg in args.args.iter_mut() {
+                if let GenericArgument::Type(ty) = arg {
+                    inner(ty);
                 }
             }
-            _ => {}
         }
     }
     inner(&mut ty);
@@ -162,7 +562,6 @@ fn turbofish(mut ty: Type) -> Type {
 
 #[cfg(test)]
 mod tests {
-    use quote::ToTokens;
     use super::*;
 
     #[test]
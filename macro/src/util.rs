@@ -1,11 +1,237 @@
-use crate::attr::FieldAttributes;
+use crate::attr::{get_docstring, ContainerAttributes, Extension, ExternalDocs, FieldAttributes};
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, TokenStream as TokenStream2};
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
 use serde_derive_internals::{
     ast::{Field, Variant},
     attr::TagType,
 };
+use syn::spanned::Spanned;
+
+/// The name a derived schema is registered under in `components.schemas`.
+pub struct ComponentName {
+    name: String,
+    qualified: bool,
+}
+
+impl ComponentName {
+    pub fn new(name: String, qualified: bool) -> Self {
+        Self { name, qualified }
+    }
+
+    /// The component for a variant of this enum, e.g. `Payment.Card`. Rust identifiers can't contain
+    /// `.`, so it can't collide with the component of another type, e.g. a `PaymentCard` struct.
+    fn variant(&self, variant: &Ident) -> Self {
+        Self::new(format!("{}.{}", self.name, variant), self.qualified)
+    }
+
+    /// Expression for the name passed to `register_schema!`.
+    fn register(&self) -> TokenStream2 {
+        let name = &self.name;
+        if self.qualified {
+            quote! { concat!(module_path!(), "::", #name) }
+        } else {
+            quote! { #name }
+        }
+    }
+
+    /// Expression for the schema reference to this component. Generic types aren't registered, so
+    /// their schema is inlined instead.
+    fn schema_ref_for(&self, generics: &syn::Generics) -> TokenStream2 {
+        if is_generic(generics) {
+            quote! { ::oasgen::ReferenceOr::Item(<Self as ::oasgen::OaSchema>::schema()) }
+        } else {
+            self.schema_ref()
+        }
+    }
+
+    /// Register the type's schema under this name, unless it's generic.
+    fn submit(&self, ident: &Ident, generics: &syn::Generics) -> TokenStream2 {
+        if is_generic(generics) {
+            return quote! {};
+        }
+        let register = self.register();
+        quote! {
+            ::oasgen::register_schema!(#register, || <#ident as ::oasgen::OaSchema>::schema());
+        }
+    }
+
+    /// Expression for the schema reference to this component.
+    fn schema_ref(&self) -> TokenStream2 {
+        let name = &self.name;
+        if self.qualified {
+            quote! {
+                ::oasgen::ReferenceOr::schema_ref(&::oasgen::__private::component_name(concat!(module_path!(), "::", #name)))
+            }
+        } else {
+            quote! { ::oasgen::ReferenceOr::schema_ref(#name) }
+        }
+    }
+}
+
+/// Generic types are inlined rather than referenced, so their recursion can't end in a reference.
+fn recursion_guard(generics: &syn::Generics) -> Ident {
+    if is_generic(generics) {
+        format_ident!("inline_recursion_guard")
+    } else {
+        format_ident!("recursion_guard")
+    }
+}
+
+/// A generic type has a schema per instantiation, so it can't be registered as a single component.
+pub fn is_generic(generics: &syn::Generics) -> bool {
+    generics.type_params().next().is_some() || generics.const_params().next().is_some()
+}
+
+/// The generics with `OaSchema` bounds. `#[oasgen(bound = "...")]` replaces the default `T: OaSchema`
+/// bound on each type parameter. Otherwise, `#[serde(bound = "...")]` is used, with the serde traits
+/// replaced by `OaSchema`.
+pub fn oaschema_generics(
+    generics: &syn::Generics,
+    bound: Option<&syn::LitStr>,
+    serde_bound: Option<&[syn::WherePredicate]>,
+) -> syn::Generics {
+    let mut generics = generics.clone();
+    let predicates: Vec<syn::WherePredicate> = if let Some(bound) = bound {
+        bound
+            .parse_with(syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated)
+            .expect("Failed to parse #[oasgen(bound)]")
+            .into_iter()
+            .collect()
+    } else if let Some(serde_bound) = serde_bound {
+        serde_bound.iter().cloned().map(serde_bound_to_oaschema).collect()
+    } else {
+        generics
+            .type_params()
+            .map(|param| {
+                let ident = &param.ident;
+                syn::parse_quote! { #ident: ::oasgen::OaSchema }
+            })
+            .collect()
+    };
+    generics.make_where_clause().predicates.extend(predicates);
+    generics
+}
+
+fn serde_bound_to_oaschema(mut predicate: syn::WherePredicate) -> syn::WherePredicate {
+    const SERDE_TRAITS: &[&str] = &["Serialize", "Deserialize", "DeserializeOwned"];
+    let syn::WherePredicate::Type(p) = &mut predicate else {
+        return predicate;
+    };
+    let mut replaced = false;
+    p.bounds = std::mem::take(&mut p.bounds)
+        .into_iter()
+        .filter_map(|bound| match &bound {
+            syn::TypeParamBound::Trait(t)
+                if t.path.segments.last().is_some_and(|s| SERDE_TRAITS.contains(&s.ident.to_string().as_str())) =>
+            {
+                (!std::mem::replace(&mut replaced, true)).then(|| syn::parse_quote! { ::oasgen::OaSchema })
+            }
+            _ => Some(bound),
+        })
+        .collect();
+    predicate
+}
+
+/// `impl OaSchema for` the type, with its generics.
+fn impl_oaschema(ident: &Ident, generics: &syn::Generics) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! { impl #impl_generics ::oasgen::OaSchema for #ident #ty_generics #where_clause }
+}
+
+/// `::oasgen::ExternalDocumentation` of an `external_docs(...)` attribute.
+pub fn external_docs(docs: &ExternalDocs) -> TokenStream2 {
+    let url = &docs.url;
+    let description = match &docs.description {
+        Some(description) => quote! { Some(#description.to_string()) },
+        None => quote! { None },
+    };
+    quote! {
+        ::oasgen::ExternalDocumentation {
+            url: #url.to_string(),
+            description: #description,
+            extensions: Default::default(),
+        }
+    }
+}
+
+/// Insert `extension(...)` attributes into the `extensions` map.
+pub fn insert_extensions(extensions_map: TokenStream2, extensions: &[Extension]) -> TokenStream2 {
+    let inserts = extensions.iter().map(|Extension { name, value }| {
+        quote! {
+            #extensions_map.insert(#name.to_string(), ::oasgen::__private::serde_json::json!(#value));
+        }
+    });
+    quote! { #(#inserts)* }
+}
+
+/// Statements setting the description, `title`, `external_docs` and `extension`s of the schema `o`.
+pub fn schema_data(
+    description: Option<&str>,
+    title: Option<&syn::LitStr>,
+    docs: Option<&ExternalDocs>,
+    extensions: Option<&Vec<Extension>>,
+) -> TokenStream2 {
+    let description = description.map(|description| quote! { o.data.description = Some(#description.into()); });
+    let title = title.map(|title| quote! { o.data.title = Some(#title.to_string()); });
+    let docs = docs.map(|docs| {
+        let docs = external_docs(docs);
+        quote! { o.data.external_docs = Some(#docs); }
+    });
+    let extensions = insert_extensions(quote! { o.data.extensions }, extensions.map(Vec::as_slice).unwrap_or_default());
+    quote! {
+        #description
+        #title
+        #docs
+        #extensions
+    }
+}
+
+/// Register the type in `components.responses` and/or `components.requestBodies`,
+/// with a JSON body referencing its schema.
+pub fn derive_components(
+    ident: &Ident,
+    name: &ComponentName,
+    attrs: &ContainerAttributes,
+    docstring: Option<&str>,
+) -> TokenStream2 {
+    let register = name.register();
+    let response = attrs.response.then(|| {
+        let description = docstring.unwrap_or(&name.name);
+        let headers = attrs.headers.iter().flatten();
+        quote! {
+            ::oasgen::register_response!(#register, || {
+                let mut response = ::oasgen::__private::json_response(
+                    #description,
+                    <#ident as ::oasgen::OaSchema>::schema_ref(),
+                );
+                #(
+                    response.headers.insert(
+                        #headers.to_string(),
+                        ::oasgen::RefOr::ref_(&format!("#/components/headers/{}", #headers)),
+                    );
+                )*
+                response
+            });
+        }
+    });
+    let request_body = attrs.request_body.then(|| {
+        let description = match docstring {
+            Some(d) => quote! { Some(#d) },
+            None => quote! { None },
+        };
+        quote! {
+            ::oasgen::register_request_body!(#register, || ::oasgen::__private::json_request_body(
+                #description,
+                <#ident as ::oasgen::OaSchema>::schema_ref(),
+            ));
+        }
+    });
+    quote! {
+        #response
+        #request_body
+    }
+}
 
 fn is_option(ty: &syn::Type) -> bool {
     let syn::Type::Path(p) = ty else {
@@ -17,7 +243,67 @@ fn is_option(ty: &syn::Type) -> bool {
     segment.ident == "Option"
 }
 
-pub fn impl_OaSchema_schema(fields: &[Field], docstring: Option<String>) -> TokenStream2 {
+/// The schema for `#[oasgen(type = "...", format = "...")]`.
+fn json_type_schema(json_type: &syn::LitStr, format: Option<&syn::LitStr>) -> TokenStream2 {
+    const TYPES: &[&str] = &["string", "integer", "number", "boolean", "object", "array"];
+    let value = json_type.value();
+    if !TYPES.contains(&value.as_str()) {
+        panic!("Unknown type `{value}` in #[oasgen(type)], expected one of: {}", TYPES.join(", "));
+    }
+    let format = format
+        .map(|format| quote! { "format": #format, })
+        .unwrap_or_default();
+    quote! {
+        ::oasgen::ReferenceOr::Item(
+            ::oasgen::__private::serde_json::from_value::<::oasgen::Schema>(
+                ::oasgen::__private::serde_json::json!({ "type": #json_type, #format })
+            ).unwrap()
+        )
+    }
+}
+
+/// The adapter of a `#[serde_as(as = "...")]` field. `#[serde_as]` rewrites the annotation to
+/// `#[serde(with = "::serde_with::As::<...>")]`, which serde reads as `serialize_with`.
+fn serde_as(field: &Field) -> Option<syn::Type> {
+    let path = &field.attrs.serialize_with()?.path;
+    let segment = path.segments.iter().find(|s| s.ident == "As")?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    }
+}
+
+/// The schema of a field serialized with a `serde_as` adapter. Without the `serde_with` feature, adapters
+/// have no `OaSchemaAs` impls, and documenting the field by its own type would misstate the wire format.
+fn serde_as_schema(field: &Field, adapter: &syn::Type, inline: bool) -> TokenStream2 {
+    if !cfg!(feature = "serde_with") {
+        let message = "#[serde_as] fields are documented by oasgen's `serde_with` feature; enable it, \
+            or override the field's schema with #[oasgen(as = ...)], #[oasgen(type = ...)] or #[oasgen(schema_with = ...)]";
+        let span = field.original.ident.as_ref().map_or(field.ty.span(), |ident| ident.span());
+        return quote_spanned! { span=> compile_error!(#message) };
+    }
+    let ty = field.ty;
+    let method = if inline { quote!(schema) } else { quote!(schema_ref) };
+    quote! {{
+        use ::oasgen::__private::{AdapterSchema as _, FieldSchema as _};
+        (&&::oasgen::__private::SchemaAs::<#adapter, #ty>::new()).#method()
+    }}
+}
+
+/// Whether the field is serialized `#[serde(with = "...")]`, which serde expands to `serialize_with = "...::serialize"`.
+/// The path names either a `#[serde(remote = "...")]` definition or a module, which is resolved when the
+/// schema is built, see `SchemaWith`.
+fn serde_with_path(field: &Field) -> bool {
+    let Some(path) = field.attrs.serialize_with().map(|p| &p.path) else {
+        return false;
+    };
+    path.segments.len() > 1 && path.segments.last().is_some_and(|s| s.ident == "serialize")
+}
+
+pub fn impl_OaSchema_schema(fields: &[Field], deny_unknown_fields: bool) -> TokenStream2 {
     if fields.len() == 1 {
         let field = fields.first().unwrap();
         if let syn::Member::Unnamed(_) = field.member {
@@ -27,44 +313,84 @@ pub fn impl_OaSchema_schema(fields: &[Field], docstring: Option<String>) -> Toke
             };
         }
     }
-    let description = docstring
-        .map(|s| {
-            quote! {
-                o.description = Some(#s.into());
-            }
-        })
-        .unwrap_or_default();
     let properties = fields
-        .into_iter()
+        .iter()
         .map(|f| {
             let mut attr = FieldAttributes::try_from(&f.original.attrs).unwrap();
-            attr.merge_serde(&f);
+            attr.merge_serde(f);
             if attr.skip {
                 return quote! {};
             }
+            if attr.format.is_some() && attr.ty.is_none() {
+                panic!("#[oasgen(format)] requires #[oasgen(type)]");
+            }
 
             let name = f.attrs.name().deserialize_name();
             let ty = f.ty;
-            let schema = quote! {
-                <#ty as ::oasgen::OaSchema>::schema()
-            };
 
             if f.attrs.flatten() {
+                // The fields of a flattened `Option` are all optional.
+                let required = if is_option(ty) {
+                    quote! {}
+                } else {
+                    quote! { o.required_mut().extend(obj.required); }
+                };
                 quote! {
-                    if let ::oasgen::SchemaKind::Type(::oasgen::Type::Object(::oasgen::ObjectType { properties, required, .. })) = #schema.kind {
-                        for (name, schema) in properties {
-                            let schema = schema.into_item().expect("Cannot flatten a reference");
-                            o.properties_mut().insert(name, schema);
+                    match <#ty as ::oasgen::OaSchema>::schema() {
+                        ::oasgen::Schema { data, kind: ::oasgen::SchemaKind::Type(::oasgen::Type::Object(obj)) } => {
+                            o.properties_mut().extend(obj.properties);
+                            #required
+                            // A flattened map collects the properties not otherwise declared.
+                            if let Some(additional_properties) = obj.additional_properties {
+                                if let ::oasgen::SchemaKind::Type(::oasgen::Type::Object(o)) = &mut o.kind {
+                                    o.additional_properties = Some(additional_properties);
+                                }
+                                if let Some(key) = data.extensions.get(::oasgen::KEY_SCHEMA_EXTENSION) {
+                                    o.data.extensions.insert(::oasgen::KEY_SCHEMA_EXTENSION.to_string(), key.clone());
+                                }
+                            }
                         }
-                        o.required_mut().extend_from_slice(&required);
+                        // Enums and recursive types can't be merged into the object, so they're combined with `allOf`.
+                        _ => all_of.push(<#ty as ::oasgen::OaSchema>::schema_ref()),
                     }
                 }
             } else {
-                let required = !(attr.skip || attr.skip_serializing_if.is_some() || is_option(ty));
-                let required = required.then(|| {
+                let required = attr.required
+                    || !(attr.skip || attr.skip_serializing_if.is_some() || is_option(ty));
+                let required = if required {
                     quote! { o.required_mut().push(#name.to_string()); }
-                }).unwrap_or_default();
-                let schema_ref = if attr.inline {
+                } else {
+                    quote! {}
+                };
+                let schema_ref = if let Some(schema_with) = &attr.schema_with {
+                    let schema_with = schema_with.parse::<syn::ExprPath>().unwrap();
+                    quote! {
+                        ::oasgen::ReferenceOr::Item(#schema_with())
+                    }
+                } else if let Some(json_type) = &attr.ty {
+                    json_type_schema(json_type, attr.format.as_ref())
+                } else if let Some(as_) = &attr.as_ {
+                    let method = if attr.inline { quote!(schema) } else { quote!(schema_ref) };
+                    quote! {
+                        <#as_ as ::oasgen::OaSchema>::#method()
+                    }
+                } else if attr.explicit_keys {
+                    quote! {
+                        <#ty as ::oasgen::MapSchema>::explicit_keys_schema()
+                    }
+                } else if attr.key_schema {
+                    quote! {
+                        <#ty as ::oasgen::MapSchema>::key_schema_ref()
+                    }
+                } else if let Some(adapter) = serde_as(f) {
+                    serde_as_schema(f, &adapter, attr.inline)
+                } else if serde_with_path(f) {
+                    let method = if attr.inline { quote!(schema) } else { quote!(schema_ref) };
+                    quote! {{
+                        use ::oasgen::__private::{RemoteSchema as _, WithSchema as _};
+                        (&&::oasgen::__private::SchemaWith::<#ty>::new()).#method()
+                    }}
+                } else if attr.inline {
                     quote! {
                         <#ty as ::oasgen::OaSchema>::schema()
                     }
@@ -73,6 +399,21 @@ pub fn impl_OaSchema_schema(fields: &[Field], docstring: Option<String>) -> Toke
                         <#ty as ::oasgen::OaSchema>::schema_ref()
                     }
                 };
+                let data = schema_data(
+                    None,
+                    attr.title.as_ref(),
+                    attr.external_docs.as_ref().map(|d| &d.args),
+                    attr.extension.as_ref(),
+                );
+                let schema_ref = if data.is_empty() {
+                    schema_ref
+                } else {
+                    quote! {{
+                        let mut o = ::oasgen::__private::schema_item(#schema_ref);
+                        #data
+                        ::oasgen::ReferenceOr::Item(o)
+                    }}
+                };
                 quote! {
                     o.properties_mut().insert(#name, #schema_ref);
                     #required
@@ -80,11 +421,36 @@ pub fn impl_OaSchema_schema(fields: &[Field], docstring: Option<String>) -> Toke
             }
         })
         .collect::<Vec<_>>();
+    let additional_properties = if deny_unknown_fields {
+        quote! {
+            if let ::oasgen::SchemaKind::Type(::oasgen::Type::Object(obj)) = &mut o.kind {
+                obj.additional_properties = Some(::oasgen::AdditionalProperties::Any(false));
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let (declare_all_of, all_of) = if fields.iter().any(|f| f.attrs.flatten()) {
+        (
+            quote! { let mut all_of: Vec<::oasgen::ReferenceOr<::oasgen::Schema>> = Vec::new(); },
+            quote! {
+                if !all_of.is_empty() {
+                    all_of.insert(0, ::oasgen::ReferenceOr::Item(o));
+                    o = ::oasgen::Schema::new_all_of(all_of);
+                }
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
     quote! {
         {
             let mut o = ::oasgen::Schema::new_object();
-            #description
+            #declare_all_of
             #(#properties)*
+            #all_of
+            // Not set on an `allOf`, whose first branch would then reject the properties of the others.
+            #additional_properties
             o
         }
     }
@@ -93,23 +459,82 @@ pub fn impl_OaSchema_schema(fields: &[Field], docstring: Option<String>) -> Toke
 /// Create OaSchema derive token stream for a struct from ident and fields
 pub fn derive_oaschema_struct(
     ident: &Ident,
+    generics: &syn::Generics,
+    name: &ComponentName,
     fields: &[Field],
-    docstring: Option<String>,
+    data: TokenStream2,
+    deny_unknown_fields: bool,
 ) -> TokenStream {
-    let schema = impl_OaSchema_schema(fields, docstring);
-    let name = ident.to_string();
-    let submit = quote! {
-        ::oasgen::register_schema!(#name, || <#ident as ::oasgen::OaSchema>::schema());
+    let schema = impl_OaSchema_schema(fields, deny_unknown_fields);
+    let schema = if data.is_empty() {
+        schema
+    } else {
+        quote! {{
+            let mut o = #schema;
+            #data
+            o
+        }}
     };
+    let impl_oaschema = impl_oaschema(ident, generics);
+    let schema_ref = name.schema_ref_for(generics);
+    let recursion_guard = recursion_guard(generics);
+    let submit = name.submit(ident, generics);
 
     quote! {
-        impl ::oasgen::OaSchema for #ident {
+        #impl_oaschema {
+            fn schema_ref() -> ::oasgen::ReferenceOr<::oasgen::Schema> {
+                #schema_ref
+            }
+
+            fn schema() -> ::oasgen::Schema {
+                ::oasgen::__private::#recursion_guard::<Self>(|| #schema)
+            }
+        }
+        #submit
+    }
+    .into()
+}
+
+/// Create OaSchema derive token stream for a tuple struct.
+/// OpenAPI 3.0 can't describe positional items, so the items are an `anyOf` of the field schemas,
+/// marked with `x-prefixItems` so they become positional `prefixItems` in OpenAPI 3.1 output.
+pub fn derive_oaschema_tuple(
+    ident: &Ident,
+    generics: &syn::Generics,
+    name: &ComponentName,
+    fields: &[Field],
+    data: TokenStream2,
+) -> TokenStream {
+    let len = fields.len();
+    let items = fields.iter().map(|f| {
+        let ty = f.ty;
+        quote! { <#ty as ::oasgen::OaSchema>::schema_ref() }
+    });
+    let impl_oaschema = impl_oaschema(ident, generics);
+    let schema_ref = name.schema_ref_for(generics);
+    let recursion_guard = recursion_guard(generics);
+    let submit = name.submit(ident, generics);
+    quote! {
+        #impl_oaschema {
             fn schema_ref() -> ::oasgen::ReferenceOr<::oasgen::Schema> {
-                ::oasgen::ReferenceOr::schema_ref(#name)
+                #schema_ref
             }
 
             fn schema() -> ::oasgen::Schema {
-                #schema
+                ::oasgen::__private::#recursion_guard::<Self>(|| {
+                    let items: Vec<::oasgen::ReferenceOr<::oasgen::Schema>> = vec![#(#items),*];
+                    let mut o = ::oasgen::Schema::new_array(::oasgen::Schema::new_any_of(items));
+                    if let ::oasgen::SchemaKind::Type(::oasgen::Type::Array(a)) = &mut o.kind {
+                        a.min_items = Some(#len);
+                        a.max_items = Some(#len);
+                    }
+                    o.data.extensions.insert(
+                        ::oasgen::PREFIX_ITEMS_EXTENSION.to_string(),
+                        ::oasgen::__private::serde_json::Value::Bool(true),
+                    );
+                    #data
+                    o
+                })
             }
         }
         #submit
@@ -120,22 +545,87 @@ pub fn derive_oaschema_struct(
 /// Create OaSchema derive token stream for an enum from ident and variants
 pub fn derive_oaschema_enum(
     ident: &Ident,
+    generics: &syn::Generics,
+    name: &ComponentName,
     variants: &[Variant],
     tag: &TagType,
-    _docstring: Option<String>,
+    data: TokenStream2,
+    deny_unknown_fields: bool,
 ) -> TokenStream {
-    let variants = variants.into_iter().filter(|v| {
-        let openapi_attrs = FieldAttributes::try_from(&v.original.attrs).unwrap();
-        !openapi_attrs.skip
-    });
+    let variants = variants
+        .iter()
+        .filter(|v| {
+            let openapi_attrs = FieldAttributes::try_from(&v.original.attrs).unwrap();
+            !openapi_attrs.skip
+        })
+        .collect::<Vec<_>>();
+    // Tagged enums with data get a discriminator. Each variant is registered as its own component,
+    // named after the enum and the variant, so the discriminator mapping can reference it.
+    let discriminator = match tag {
+        TagType::Internal { tag } | TagType::Adjacent { tag, .. }
+            if !is_generic(generics) && variants.iter().any(|v| !v.fields.is_empty()) => Some(tag),
+        _ => None,
+    };
+    let enum_name = name;
+    let mut variant_components = vec![];
+    let mut variant_refs = vec![];
+    let mut mapping = vec![];
     let mut complex_variants = vec![];
+    // Title and description of each `oneOf` branch.
+    let mut branch_docs = vec![];
     let mut str_variants = vec![];
+    let mut str_names = vec![];
+    let mut str_varnames = vec![];
+    let mut str_descriptions = vec![];
+    // `#[serde(alias)]`es of unit variants, which are only accepted when deserializing.
+    let mut str_aliases = vec![];
+    // A `#[serde(other)]` variant accepts any tag, so the tag values are open-ended.
+    let has_other = variants.iter().any(|v| v.attrs.other());
+    // Tags of the other variants, which the `#[serde(other)]` variant doesn't match.
+    let mut known_tags = vec![];
+    for v in variants.iter().filter(|v| !v.attrs.other()) {
+        let name = v.attrs.name().deserialize_name();
+        known_tags.push(name.to_string());
+        known_tags.extend(v.attrs.aliases().iter().filter(|alias| *alias != name).cloned());
+    }
     for v in variants {
         let name = v.attrs.name().deserialize_name();
-        if v.fields.len() == 0 {
+        // Deserializing also accepts `#[serde(alias)]`es of the variant name.
+        let aliases = v.attrs.aliases().iter().filter(|alias| *alias != name).collect::<Vec<_>>();
+        let variant_docstring = get_docstring(&v.original.attrs).expect("Failed to parse docstring");
+        if v.fields.is_empty() && discriminator.is_none() {
             str_variants.push(quote! { #name.to_string(), });
+            str_names.push(name.to_string());
+            str_varnames.push(v.ident.to_string());
+            str_descriptions.push(variant_docstring.clone());
+            for alias in aliases {
+                str_aliases.push(quote! { #alias: #name });
+            }
         } else {
-            let schema = impl_OaSchema_schema(&v.fields, None);
+            let schema = impl_OaSchema_schema(&v.fields, deny_unknown_fields);
+            let tag_schema = if v.attrs.other() {
+                let known_tags = known_tags.iter();
+                quote! {
+                    ::oasgen::Schema {
+                        data: ::oasgen::SchemaData::default(),
+                        kind: ::oasgen::SchemaKind::AllOf {
+                            all_of: vec![
+                                ::oasgen::ReferenceOr::Item(::oasgen::Schema::new_string()),
+                                ::oasgen::ReferenceOr::Item(::oasgen::Schema {
+                                    data: ::oasgen::SchemaData::default(),
+                                    kind: ::oasgen::SchemaKind::Not {
+                                        not: Box::new(::oasgen::ReferenceOr::Item(
+                                            ::oasgen::Schema::new_str_enum(vec![#(#known_tags.to_string()),*])
+                                        )),
+                                    },
+                                }),
+                            ],
+                        },
+                    }
+                }
+            } else {
+                quote! { ::oasgen::Schema::new_str_enum(vec![#name.to_string(), #(#aliases.to_string()),*]) }
+            };
             let variant = match tag {
                 TagType::External => quote! {
                     {
@@ -150,13 +640,13 @@ pub fn derive_oaschema_enum(
                         let mut o = #schema;
                         match o.kind {
                             ::oasgen::SchemaKind::Type(_) => {
-                                o.properties_mut().insert(#tag, ::oasgen::Schema::new_str_enum(vec![#name.to_string()]));
+                                o.properties_mut().insert(#tag, #tag_schema);
                                 o.required_mut().push(#tag.to_string());
                                 o
                             }
                             _ => {
                                 let mut t = ::oasgen::Schema::new_object();
-                                t.properties_mut().insert(#tag, ::oasgen::Schema::new_str_enum(vec![#name.to_string()]));
+                                t.properties_mut().insert(#tag, #tag_schema);
                                 t.required_mut().push(#tag.to_string());
 
                                 ::oasgen::Schema {
@@ -172,11 +662,19 @@ pub fn derive_oaschema_enum(
                         }
                     }
                 },
+                // Unit variants are serialized without content.
+                TagType::Adjacent { tag, .. } if v.fields.is_empty() => quote! {
+                    {
+                        let mut o = ::oasgen::Schema::new_object();
+                        o.properties_mut().insert(#tag, #tag_schema);
+                        o.required_mut().push(#tag.to_string());
+                        o
+                    }
+                },
                 TagType::Adjacent { tag, content } => quote! {
                     {
                         let mut o = ::oasgen::Schema::new_object();
-                        let values = vec![#name.to_string()];
-                        o.properties_mut().insert(#tag, ::oasgen::Schema::new_str_enum(values));
+                        o.properties_mut().insert(#tag, #tag_schema);
                         o.properties_mut().insert(#content, #schema);
                         let required = o.required_mut();
                         required.push(#tag.to_string());
@@ -186,62 +684,248 @@ pub fn derive_oaschema_enum(
                 },
                 TagType::None => schema,
             };
-            complex_variants.push(variant);
+            let title = v.ident.to_string();
+            let description = variant_docstring
+                .map(|s| quote! { o.data.description = Some(#s.into()); })
+                .unwrap_or_default();
+            let docs = quote! {
+                o.data.title = Some(#title.into());
+                #description
+            };
+            if discriminator.is_some() {
+                let component = enum_name.variant(&v.ident);
+                let register = component.register();
+                let schema_ref = component.schema_ref();
+                variant_components.push(quote! {
+                    ::oasgen::register_schema!(#register, || {
+                        let mut o = #variant;
+                        #docs
+                        o
+                    });
+                });
+                if !v.attrs.other() {
+                    mapping.push(quote! {
+                        let schema_ref: ::oasgen::ReferenceOr<::oasgen::Schema> = #schema_ref;
+                        if let ::oasgen::ReferenceOr::Reference { reference } = schema_ref {
+                            for value in [#name, #(#aliases),*] {
+                                mapping.insert(value.to_string(), reference.clone());
+                            }
+                        }
+                    });
+                }
+                variant_refs.push(schema_ref);
+            } else {
+                complex_variants.push(variant);
+                branch_docs.push(docs);
+            }
         }
     }
 
-    if str_variants.len() > 0 {
+    // Rust names and docs of unit variants, for generated clients. Only emitted if they add something.
+    let mut str_enum_extensions = vec![];
+    if str_varnames.iter().zip(&str_names).any(|(ident, name)| ident != name) {
+        str_enum_extensions.push(quote! {
+            o.data.extensions.insert(
+                "x-enum-varnames".to_string(),
+                ::oasgen::__private::serde_json::json!([#(#str_varnames),*]),
+            );
+        });
+    }
+    if str_descriptions.iter().any(Option::is_some) {
+        let str_descriptions = str_descriptions.iter().map(|d| d.as_deref().unwrap_or_default());
+        str_enum_extensions.push(quote! {
+            o.data.extensions.insert(
+                "x-enum-descriptions".to_string(),
+                ::oasgen::__private::serde_json::json!([#(#str_descriptions),*]),
+            );
+        });
+    }
+    if !str_aliases.is_empty() {
+        str_enum_extensions.push(quote! {
+            o.data.extensions.insert(
+                "x-enum-aliases".to_string(),
+                ::oasgen::__private::serde_json::json!({#(#str_aliases),*}),
+            );
+        });
+    }
+    if has_other {
+        str_enum_extensions.push(quote! {
+            if let ::oasgen::SchemaKind::Type(::oasgen::Type::String(s)) = &mut o.kind {
+                let values = std::mem::take(&mut s.enumeration);
+                o.data.extensions.insert(
+                    "x-extensible-enum".to_string(),
+                    ::oasgen::__private::serde_json::json!(values),
+                );
+            }
+        });
+    }
+    // Unit variants of untagged enums are serialized as `null`.
+    let untagged_unit = matches!(tag, TagType::None) && !str_variants.is_empty();
+    if !str_variants.is_empty() {
+        let str_enum = quote! {{
+            let mut o = ::oasgen::Schema::new_str_enum(vec![#(#str_variants)*]);
+            #(#str_enum_extensions)*
+            o
+        }};
         match tag {
-            TagType::External => complex_variants
-                .push(quote! { ::oasgen::Schema::new_str_enum(vec![#(#str_variants)*]) }),
+            TagType::External => complex_variants.push(str_enum),
             TagType::Internal { tag } | TagType::Adjacent { tag, .. } => {
                 complex_variants.push(quote! {{
                     let mut o = ::oasgen::Schema::new_object();
-                    let values = vec![#(#str_variants)*];
-                    o.properties_mut().insert(#tag, ::oasgen::Schema::new_str_enum(values));
+                    o.properties_mut().insert(#tag, #str_enum);
                     o.required_mut().push(#tag.to_string());
                     o
                 }})
             }
-            _ => (), // a null case should be handled, which will deserialize to the first unit
-                     // variant, but unsure how to handle this case. I tried an enum with
-                     // type: 'null', which is supported in glademiller:openapiv3, but not in
-                     // kurtbuilds:openapiv3
-                     // kurt: I believe null enum is handled by setting nullable: true.
+            TagType::None => {}
         }
     }
 
-    let schema = if complex_variants.len() == 1 {
+    let schema = if let Some(tag) = discriminator {
+        quote! {{
+            let mut mapping = ::oasgen::IndexMap::new();
+            #(#mapping)*
+            let mut o = ::oasgen::Schema::new_one_of(vec![#(#variant_refs),*]);
+            o.data.discriminator = Some(::oasgen::Discriminator {
+                property_name: #tag.to_string(),
+                mapping,
+                extensions: Default::default(),
+            });
+            o
+        }}
+    } else if complex_variants.is_empty() {
+        quote! { <::std::marker::PhantomData<()> as ::oasgen::OaSchema>::schema() }
+    } else if complex_variants.len() == 1 {
         complex_variants.pop().unwrap()
     } else {
+        branch_docs.resize(complex_variants.len(), quote! {});
         quote! {
-            ::oasgen::Schema::new_one_of(vec![#(::oasgen::ReferenceOr::Item(#complex_variants)),*])
+            ::oasgen::Schema::new_one_of(vec![#(::oasgen::ReferenceOr::Item({
+                let mut o = #complex_variants;
+                #branch_docs
+                o
+            })),*])
         }
     };
 
-    let name = ident.to_string();
-    let submit = quote! {
-        ::oasgen::register_schema!(#name, || <#ident as ::oasgen::OaSchema>::schema());
+    let schema = if untagged_unit {
+        quote! {{
+            let mut o = #schema;
+            o.data.nullable = true;
+            o
+        }}
+    } else {
+        schema
     };
+
+    let schema = if data.is_empty() {
+        schema
+    } else {
+        quote! {{
+            let mut o = #schema;
+            #data
+            o
+        }}
+    };
+
+    let impl_oaschema = impl_oaschema(ident, generics);
+    let schema_ref = name.schema_ref_for(generics);
+    let recursion_guard = recursion_guard(generics);
+    let submit = name.submit(ident, generics);
     quote! {
-        impl ::oasgen::OaSchema for #ident {
+        #impl_oaschema {
             fn schema_ref() -> ::oasgen::RefOr<::oasgen::Schema> {
-                ::oasgen::RefOr::schema_ref(#name)
+                #schema_ref
             }
 
             fn schema() -> ::oasgen::Schema {
-                #schema
+                ::oasgen::__private::#recursion_guard::<Self>(|| #schema)
             }
         }
         #submit
+        #(#variant_components)*
     }
     .into()
 }
 
-pub fn derive_oaschema_newtype(ident: &Ident, field: &Field) -> TokenStream {
-    let ty = &field.ty;
+/// Create OaSchema derive token stream for a fieldless enum serialized as its discriminant.
+pub fn derive_oaschema_repr_enum(
+    ident: &Ident,
+    name: &ComponentName,
+    variants: &[Variant],
+    repr: Option<Ident>,
+    data: TokenStream2,
+) -> TokenStream {
+    let repr = repr.unwrap_or_else(|| Ident::new("isize", proc_macro2::Span::call_site()));
+    let values = variants.iter().map(|v| {
+        let variant = &v.ident;
+        quote! { Some(#ident::#variant as i64) }
+    });
+    // Integer enums in the spec are `i64`s. Reject discriminants of wider types that don't fit,
+    // rather than wrapping them.
+    let range_checks = variants.iter().filter_map(|v| {
+        let variant = &v.ident;
+        let message = format!("the discriminant of `{ident}::{variant}` doesn't fit in an i64, so it can't be documented");
+        let fits = match repr.to_string().as_str() {
+            "u64" | "usize" | "u128" => quote! { #ident::#variant as u128 <= i64::MAX as u128 },
+            "i128" => quote! { #ident::#variant as i128 >= i64::MIN as i128 && #ident::#variant as i128 <= i64::MAX as i128 },
+            _ => return None,
+        };
+        Some(quote! {
+            const _: () = assert!(#fits, #message);
+        })
+    });
+    let varnames = variants.iter().map(|v| v.ident.to_string());
+    let descriptions = variants
+        .iter()
+        .map(|v| get_docstring(&v.original.attrs).expect("Failed to parse docstring"))
+        .collect::<Vec<_>>();
+    let descriptions = descriptions.iter().any(Option::is_some).then(|| {
+        let descriptions = descriptions.iter().map(|d| d.as_deref().unwrap_or_default());
+        quote! {
+            o.data.extensions.insert(
+                "x-enum-descriptions".to_string(),
+                ::oasgen::__private::serde_json::json!([#(#descriptions),*]),
+            );
+        }
+    });
+    let schema_ref = name.schema_ref();
+    let submit = name.submit(ident, &syn::Generics::default());
     quote! {
+        #(#range_checks)*
+
         impl ::oasgen::OaSchema for #ident {
+            fn schema_ref() -> ::oasgen::ReferenceOr<::oasgen::Schema> {
+                #schema_ref
+            }
+
+            fn schema() -> ::oasgen::Schema {
+                let mut o = <#repr as ::oasgen::OaSchema>::schema();
+                if let ::oasgen::SchemaKind::Type(::oasgen::Type::Integer(i)) = &mut o.kind {
+                    // The values are the bounds.
+                    i.minimum = None;
+                    i.maximum = None;
+                    i.enumeration = vec![#(#values),*];
+                }
+                o.data.extensions.insert(
+                    "x-enum-varnames".to_string(),
+                    ::oasgen::__private::serde_json::json!([#(#varnames),*]),
+                );
+                #descriptions
+                #data
+                o
+            }
+        }
+        #submit
+    }
+    .into()
+}
+
+pub fn derive_oaschema_newtype(ident: &Ident, generics: &syn::Generics, field: &Field) -> TokenStream {
+    let ty = &field.ty;
+    let impl_oaschema = impl_oaschema(ident, generics);
+    quote! {
+        #impl_oaschema {
             fn schema_ref() -> ::oasgen::RefOr<::oasgen::Schema> {
                 <#ty as OaSchema>::schema_ref()
             }
@@ -7,11 +7,13 @@ mod inner {
     use oasgen::{oasgen, OaSchema, Server};
     use serde::{Deserialize, Serialize};
 
+    #[allow(dead_code)]
     #[derive(OaSchema, Deserialize)]
     pub struct SendCode {
         pub mobile: String,
     }
 
+    #[allow(dead_code)]
     #[derive(OaSchema, Deserialize)]
     pub struct VerifyCode {
         pub mobile: String,
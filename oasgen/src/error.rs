@@ -0,0 +1,78 @@
+use std::fmt;
+
+use http::Method;
+
+use crate::Diagnostic;
+
+/// Problems found while building the spec.
+///
+/// `Server` collects these instead of panicking, so that every problem can be reported at once
+/// by `Server::try_freeze`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OasgenError {
+    /// The path is already in the spec as a `$ref`, so operations can't be added to it.
+    PathItemReference { path: String },
+    /// The webhook is already in the spec as something other than a path item, e.g. a `$ref`,
+    /// so operations can't be added to it.
+    WebhookReference { name: String },
+    /// The HTTP method has no corresponding field on `PathItem`.
+    UnsupportedMethod { path: String, method: Method },
+    /// The Swagger UI route must end with a slash, otherwise static resources will not be found.
+    SwaggerUiMissingTrailingSlash { route: String },
+    /// Swagger UI needs a spec to point at. Call `route_json_spec` or `route_yaml_spec` first.
+    SwaggerUiMissingSpecRoute { route: String },
+    /// The spec could not be serialized to the given format.
+    Serialization { format: &'static str, message: String },
+    /// The spec failed validation. Only reported when `Server::validate_on_freeze` is set, except for
+    /// component name collisions, which `try_freeze` and `try_generate_openapi` always report.
+    Invalid(Diagnostic),
+    /// A standalone JSON Schema references schemas that were never registered, so they can't be bundled.
+    UnresolvedReferences { references: Vec<String> },
+}
+
+impl fmt::Display for OasgenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OasgenError::PathItemReference { path } => {
+                write!(f, "{path}: Currently don't support references for PathItem.")
+            }
+            OasgenError::WebhookReference { name } => {
+                write!(f, "Webhook {name}: Currently don't support references for PathItem.")
+            }
+            OasgenError::UnsupportedMethod { path, method } => {
+                write!(f, "{path}: Unsupported method: {method}")
+            }
+            OasgenError::SwaggerUiMissingTrailingSlash { route } => write!(
+                f,
+                "{route}: Swagger UI route must end with a slash. Without it, static resources will not be found."
+            ),
+            OasgenError::SwaggerUiMissingSpecRoute { route } => write!(
+                f,
+                "{route}: Tried to create Swagger UI route, but no JSON or YAML route was set. \
+                On `oasgen::Server` instance, call `route_yaml_spec` or `route_json_spec`. \
+                If you manually create the route, set the field, call this method, then set the field to None."
+            ),
+            OasgenError::Serialization { format, message } => {
+                write!(f, "Serializing OpenAPI spec to {format} failed: {message}")
+            }
+            OasgenError::Invalid(diagnostic) => write!(f, "{diagnostic}"),
+            OasgenError::UnresolvedReferences { references } => write!(
+                f,
+                "References to unregistered schemas can't be resolved: {}",
+                references.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OasgenError {}
+
+impl OasgenError {
+    pub(crate) fn json(e: serde_json::Error) -> Self {
+        OasgenError::Serialization { format: "JSON", message: e.to_string() }
+    }
+
+    pub(crate) fn yaml(e: serde_yaml::Error) -> Self {
+        OasgenError::Serialization { format: "YAML", message: e.to_string() }
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+
+use oasgen_core::{OaSchema, RefOr, SchemaRegister};
+use serde_yaml::{Mapping, Value};
+
+use crate::prune::{schema_refs, SCHEMA_PREFIX};
+use crate::OasgenError;
+use crate::version::convert_schema;
+
+/// The JSON Schema dialect to emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Draft {
+    #[default]
+    Draft2020_12,
+    Draft07,
+}
+
+impl Draft {
+    fn meta_schema(self) -> &'static str {
+        match self {
+            Draft::Draft2020_12 => "https://json-schema.org/draft/2020-12/schema",
+            Draft::Draft07 => "http://json-schema.org/draft-07/schema#",
+        }
+    }
+
+    fn defs_key(self) -> &'static str {
+        match self {
+            Draft::Draft2020_12 => "$defs",
+            Draft::Draft07 => "definitions",
+        }
+    }
+}
+
+/// A self-contained JSON Schema (draft 2020-12) for `T`.
+///
+/// Every schema `T` references is looked up among the registered schemas (see `register_schema!`)
+/// and bundled under `$defs`. References to schemas that were never registered can't be resolved
+/// in a standalone document, so they're reported as `OasgenError::UnresolvedReferences`.
+///
+/// Like [`crate::to_openapi_3_1`], the result is a `serde_yaml::Value`, which keeps keys in order.
+/// Serialize it with `serde_json` for JSON.
+pub fn json_schema<T: OaSchema>() -> Result<Value, OasgenError> {
+    json_schema_with_draft::<T>(Draft::default())
+}
+
+/// Like `json_schema`, for the given JSON Schema draft.
+pub fn json_schema_with_draft<T: OaSchema>(draft: Draft) -> Result<Value, OasgenError> {
+    let registered = inventory::iter::<SchemaRegister>
+        .into_iter()
+        .map(|flag| (crate::__private::component_name(flag.name), flag))
+        .collect::<HashMap<_, _>>();
+
+    // A recursive type refers to itself by name. Those references point at the root, `#`, instead of a copy in `$defs`.
+    let root_name = match T::schema_ref() {
+        RefOr::Reference { reference } => reference.strip_prefix(SCHEMA_PREFIX).map(str::to_string),
+        RefOr::Item(_) => None,
+    };
+    let mut root = serde_yaml::to_value(T::schema()).expect("Serializing schema failed.");
+    let mut defs = Vec::new();
+    let mut unresolved = vec![];
+    let mut queue = schema_refs(&root);
+    while let Some(name) = queue.pop() {
+        if root_name.as_ref() == Some(&name) || defs.iter().any(|(n, _)| *n == name) || unresolved.contains(&name) {
+            continue;
+        }
+        let Some(flag) = registered.get(&name) else {
+            unresolved.push(name);
+            continue;
+        };
+        let schema = serde_yaml::to_value((flag.constructor)()).expect("Serializing schema failed.");
+        queue.extend(schema_refs(&schema));
+        defs.push((name, schema));
+    }
+    if !unresolved.is_empty() {
+        unresolved.sort();
+        return Err(OasgenError::UnresolvedReferences { references: unresolved });
+    }
+    defs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let prefix = format!("#/{}/", draft.defs_key());
+    let bundled = defs.iter().map(|(name, _)| name.clone()).collect::<HashSet<_>>();
+    for schema in std::iter::once(&mut root).chain(defs.iter_mut().map(|(_, schema)| schema)) {
+        convert_schema(schema);
+        rewrite_refs(schema, &prefix, &bundled, root_name.as_deref());
+        if draft == Draft::Draft07 {
+            to_draft_07(schema);
+        }
+    }
+
+    let mut bundle = Mapping::new();
+    bundle.insert("$schema".into(), draft.meta_schema().into());
+    if let Value::Mapping(root) = root {
+        bundle.extend(root);
+    }
+    if !defs.is_empty() {
+        let defs = defs.into_iter().map(|(name, schema)| (name.into(), schema)).collect();
+        bundle.insert(draft.defs_key().into(), Value::Mapping(defs));
+    }
+    Ok(Value::Mapping(bundle))
+}
+
+/// Point references to bundled schemas at the definitions in the bundle, and references to the root at `#`.
+fn rewrite_refs(value: &mut Value, prefix: &str, bundled: &HashSet<String>, root: Option<&str>) {
+    match value {
+        Value::Mapping(map) => {
+            if let Some(Value::String(reference)) = map.get_mut("$ref")
+                && let Some(name) = reference.strip_prefix(SCHEMA_PREFIX)
+            {
+                if root == Some(name) {
+                    *reference = "#".to_string();
+                } else if bundled.contains(name) {
+                    *reference = format!("{prefix}{name}");
+                }
+            }
+            map.values_mut().for_each(|v| rewrite_refs(v, prefix, bundled, root));
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(|v| rewrite_refs(v, prefix, bundled, root)),
+        _ => {}
+    }
+}
+
+/// Draft-07 has no `prefixItems`, and ignores keywords next to `$ref`.
+fn to_draft_07(value: &mut Value) {
+    match value {
+        Value::Mapping(map) => {
+            map.values_mut().for_each(to_draft_07);
+            if let Some(prefix_items) = map.shift_remove("prefixItems") {
+                map.insert("items".into(), prefix_items);
+                map.insert("additionalItems".into(), Value::Bool(false));
+            }
+            if map.len() > 1 && let Some(reference) = map.shift_remove("$ref") {
+                let mut inner = Mapping::new();
+                inner.insert("$ref".into(), reference);
+                map.insert("allOf".into(), Value::Sequence(vec![Value::Mapping(inner)]));
+            }
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(to_draft_07),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oasgen_core::Schema;
+    use serde_json::json;
+
+    struct Order;
+
+    impl OaSchema for Order {
+        fn schema_ref() -> RefOr<Schema> {
+            RefOr::schema_ref("Order")
+        }
+
+        fn schema() -> Schema {
+            let mut o = Schema::new_object();
+            o.properties_mut().insert("customer", RefOr::schema_ref("Customer"));
+            o
+        }
+    }
+
+    struct Cart;
+
+    impl OaSchema for Cart {
+        fn schema() -> Schema {
+            let mut o = Schema::new_object();
+            o.properties_mut().insert("customer", RefOr::schema_ref("Customer"));
+            o.properties_mut().insert("items", Schema::new_array(RefOr::schema_ref("Item")));
+            o.properties_mut().insert("coupon", RefOr::schema_ref("Coupon"));
+            o
+        }
+    }
+
+    struct Category;
+
+    impl OaSchema for Category {
+        fn schema_ref() -> RefOr<Schema> {
+            RefOr::schema_ref("Category")
+        }
+
+        fn schema() -> Schema {
+            let mut o = Schema::new_object();
+            o.properties_mut().insert("children", Schema::new_array(RefOr::schema_ref("Category")));
+            o
+        }
+    }
+
+    inventory::submit!(SchemaRegister {
+        name: "Category",
+        module_path: module_path!(),
+        constructor: &|| Category::schema(),
+    });
+
+    inventory::submit!(SchemaRegister {
+        name: "Customer",
+        module_path: module_path!(),
+        constructor: &|| {
+            let mut o = Schema::new_object();
+            o.properties_mut().insert("name", Schema::new_string());
+            o
+        },
+    });
+
+    #[test]
+    fn test_json_schema() {
+        let schema = json_schema::<Order>().unwrap();
+        let keys = schema.as_mapping().unwrap().keys().collect::<Vec<_>>();
+        assert_eq!(keys, ["$schema", "type", "properties", "$defs"]);
+        assert_eq!(schema, yaml(json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "customer": {"$ref": "#/$defs/Customer"},
+            },
+            "$defs": {
+                "Customer": {"type": "object", "properties": {"name": {"type": "string"}}},
+            },
+        })));
+
+        let schema = json_schema_with_draft::<Order>(Draft::Draft07).unwrap();
+        assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+        assert_eq!(schema["properties"]["customer"], yaml(json!({"$ref": "#/definitions/Customer"})));
+        assert!(schema["definitions"]["Customer"].is_mapping());
+    }
+
+    fn yaml(value: serde_json::Value) -> Value {
+        serde_yaml::to_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_recursive_root() {
+        let schema = json_schema::<Category>().unwrap();
+        assert_eq!(schema, yaml(json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "children": {"type": "array", "items": {"$ref": "#"}},
+            },
+        })));
+    }
+
+    #[test]
+    fn test_unresolved_references() {
+        assert_eq!(
+            json_schema::<Cart>(),
+            Err(OasgenError::UnresolvedReferences { references: vec!["Coupon".to_string(), "Item".to_string()] }),
+        );
+    }
+}
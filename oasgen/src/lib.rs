@@ -3,8 +3,18 @@
 #![allow(unused)]
 mod server;
 mod format;
+mod error;
+mod validate;
+mod prune;
+mod version;
+mod json_schema;
 
 pub use format::*;
+pub use error::OasgenError;
+pub use validate::{validate, Diagnostic};
+pub use prune::prune_schemas;
+pub use version::{to_openapi_3_1, OpenApiVersion};
+pub use json_schema::{json_schema, json_schema_with_draft, Draft};
 pub use oasgen_macro::{OaSchema, oasgen};
 pub use server::Server;
 pub use oasgen_core::*;
@@ -15,43 +25,403 @@ pub use swagger_ui;
 
 pub mod __private {
     pub use inventory;
-    pub use oasgen_core::{SchemaRegister, OperationRegister};
+    pub use serde_json;
+    pub use oasgen_core::{SchemaRegister, ComponentRegister, RemoteRegister};
+    use std::cell::RefCell;
+    use std::marker::PhantomData;
+    use oasgen_core::{
+        Documented, IndexMap, MediaType, OaOperation, OaSchema, OaSchemaAs, RefOr, RequestBody, Response, Schema,
+        SchemaData, SchemaKind,
+    };
 
     pub fn fn_path_to_op_id(type_name: &str) -> Option<String> {
         Some(type_name.split("::").skip(1).collect::<Vec<_>>().join("_"))
     }
+
+    /// Tag the future of an `#[oasgen]` handler with its operation. Naming the output lets the compiler infer
+    /// the return type of the handler's body, as it would for an `async fn`.
+    pub fn documented<O, R, F>(operation: O, future: F) -> Documented<O, F>
+    where
+        O: OaOperation,
+        F: std::future::Future<Output = R>,
+    {
+        Documented::new(operation, future)
+    }
+
+    /// Component names can't contain `::`, so module paths are joined with `.` instead.
+    pub fn component_name(name: &str) -> String {
+        name.replace("::", ".")
+    }
+
+    thread_local! {
+        /// Types whose schemas are being built, innermost last.
+        static BUILDING: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+
+    struct Building;
+
+    impl Drop for Building {
+        fn drop(&mut self) {
+            BUILDING.with(|building| building.borrow_mut().pop());
+        }
+    }
+
+    /// Build the schema of `T`. If it's already being built further up the stack, i.e. `T` is recursive,
+    /// return a reference to it instead, so inlined and flattened recursive types don't recurse forever.
+    pub fn recursion_guard<T: OaSchema + ?Sized>(build: impl FnOnce() -> Schema) -> Schema {
+        guard::<T>(build, || Schema {
+            data: Default::default(),
+            kind: SchemaKind::AllOf { all_of: vec![T::schema_ref()] },
+        })
+    }
+
+    /// Like `recursion_guard`, for generic types. They aren't registered, so there's nothing to reference,
+    /// and the recursion ends in a schema accepting any value instead.
+    pub fn inline_recursion_guard<T: OaSchema + ?Sized>(build: impl FnOnce() -> Schema) -> Schema {
+        guard::<T>(build, || Schema {
+            data: SchemaData {
+                description: Some(format!("Recursive `{}`, not expanded further.", short_type_name::<T>())),
+                ..SchemaData::default()
+            },
+            kind: SchemaKind::Any(Default::default()),
+        })
+    }
+
+    fn guard<T: ?Sized>(build: impl FnOnce() -> Schema, recursive: impl FnOnce() -> Schema) -> Schema {
+        let name = std::any::type_name::<T>();
+        if BUILDING.with(|building| building.borrow().contains(&name)) {
+            return recursive();
+        }
+        BUILDING.with(|building| building.borrow_mut().push(name));
+        let _building = Building;
+        build()
+    }
+
+    /// The type name without module paths, e.g. `Tree<i32>` for `app::Tree<i32>`.
+    fn short_type_name<T: ?Sized>() -> String {
+        let mut short = String::new();
+        let mut path = String::new();
+        for c in std::any::type_name::<T>().chars() {
+            if c.is_alphanumeric() || c == '_' || c == ':' {
+                path.push(c);
+            } else {
+                short.push_str(path.rsplit("::").next().unwrap_or_default());
+                path.clear();
+                short.push(c);
+            }
+        }
+        short.push_str(path.rsplit("::").next().unwrap_or_default());
+        short
+    }
+
+    /// Schema of a field `T` serialized with the `serde_as` adapter `A`. Called as
+    /// `(&&SchemaAs::<A, T>::new()).schema_ref()`, so method resolution picks `AdapterSchema` if `A`
+    /// implements `OaSchemaAs<T>`, and falls back to `FieldSchema`, the schema of `T` itself, otherwise.
+    pub struct SchemaAs<A, T>(PhantomData<fn() -> (A, T)>);
+
+    impl<A, T> SchemaAs<A, T> {
+        pub fn new() -> Self {
+            SchemaAs(PhantomData)
+        }
+    }
+
+    impl<A, T> Default for SchemaAs<A, T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    pub trait AdapterSchema {
+        fn schema(&self) -> Schema;
+        fn schema_ref(&self) -> RefOr<Schema>;
+    }
+
+    impl<A: OaSchemaAs<T>, T> AdapterSchema for &SchemaAs<A, T> {
+        fn schema(&self) -> Schema {
+            A::schema()
+        }
+
+        fn schema_ref(&self) -> RefOr<Schema> {
+            A::schema_ref()
+        }
+    }
+
+    pub trait FieldSchema {
+        fn schema(&self) -> Schema;
+        fn schema_ref(&self) -> RefOr<Schema>;
+    }
+
+    impl<A, T: OaSchema> FieldSchema for SchemaAs<A, T> {
+        fn schema(&self) -> Schema {
+            T::schema()
+        }
+
+        fn schema_ref(&self) -> RefOr<Schema> {
+            T::schema_ref()
+        }
+    }
+
+    /// Schema of a field `T` serialized `#[serde(with = "...")]`. The path may name a `#[serde(remote = "...")]`
+    /// definition or a module, which the derive can't tell apart, so the definition is looked up by the
+    /// remote type it registered. Called as `(&&SchemaWith::<T>::new()).schema_ref()`: if `T` has its own
+    /// schema, `WithSchema` falls back to it, otherwise `RemoteSchema` panics if no definition is registered.
+    pub struct SchemaWith<T: ?Sized>(PhantomData<fn() -> T>);
+
+    impl<T: ?Sized> SchemaWith<T> {
+        pub fn new() -> Self {
+            SchemaWith(PhantomData)
+        }
+    }
+
+    impl<T: ?Sized> Default for SchemaWith<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    fn remote_definition<T: ?Sized>() -> Option<&'static RemoteRegister> {
+        let type_name = std::any::type_name::<T>();
+        inventory::iter::<RemoteRegister>.into_iter().find(|r| (r.type_name)() == type_name)
+    }
+
+    pub trait WithSchema {
+        fn schema(&self) -> Schema;
+        fn schema_ref(&self) -> RefOr<Schema>;
+    }
+
+    impl<T: OaSchema + ?Sized> WithSchema for &SchemaWith<T> {
+        fn schema(&self) -> Schema {
+            remote_definition::<T>().map_or_else(T::schema, |r| (r.schema)())
+        }
+
+        fn schema_ref(&self) -> RefOr<Schema> {
+            remote_definition::<T>().map_or_else(T::schema_ref, |r| (r.schema_ref)())
+        }
+    }
+
+    pub trait RemoteSchema {
+        fn schema(&self) -> Schema;
+        fn schema_ref(&self) -> RefOr<Schema>;
+    }
+
+    impl<T: ?Sized> RemoteSchema for SchemaWith<T> {
+        fn schema(&self) -> Schema {
+            (Self::definition().schema)()
+        }
+
+        fn schema_ref(&self) -> RefOr<Schema> {
+            (Self::definition().schema_ref)()
+        }
+    }
+
+    impl<T: ?Sized> SchemaWith<T> {
+        fn definition() -> &'static RemoteRegister {
+            remote_definition::<T>().unwrap_or_else(|| panic!(
+                "`{}` has no schema. Derive OaSchema on its #[serde(remote = \"...\")] definition, \
+                or describe the field with #[oasgen(schema_with = \"...\")]",
+                std::any::type_name::<T>(),
+            ))
+        }
+    }
+
+    /// The schema, with references wrapped in `allOf`, so keywords can be added next to them.
+    /// OpenAPI 3.0 ignores keywords next to a `$ref`.
+    pub fn schema_item(schema: RefOr<Schema>) -> Schema {
+        match schema {
+            RefOr::Item(schema) => schema,
+            reference => Schema {
+                data: Default::default(),
+                kind: SchemaKind::AllOf { all_of: vec![reference] },
+            },
+        }
+    }
+
+    fn json_content(schema: RefOr<Schema>) -> IndexMap<String, MediaType> {
+        let mut content = IndexMap::new();
+        content.insert("application/json".to_string(), MediaType {
+            schema: Some(schema),
+            ..MediaType::default()
+        });
+        content
+    }
+
+    pub fn json_response(description: &str, schema: RefOr<Schema>) -> Response {
+        Response {
+            description: description.to_string(),
+            content: json_content(schema),
+            ..Response::default()
+        }
+    }
+
+    pub fn json_request_body(description: Option<&str>, schema: RefOr<Schema>) -> RequestBody {
+        RequestBody {
+            description: description.map(|s| s.to_string()),
+            content: json_content(schema),
+            required: true,
+            ..RequestBody::default()
+        }
+    }
 }
 
 #[macro_export]
 macro_rules! register_schema {
-    ($name:literal, $constructor:expr) => {
+    ($name:expr, $constructor:expr) => {
         ::oasgen::__private::inventory::submit!(::oasgen::__private::SchemaRegister {
             name: $name,
+            module_path: module_path!(),
             constructor: &$constructor,
         });
     };
 }
 
+/// Register a reusable parameter in `components.parameters`.
+/// Reference it from a handler with `#[oasgen(parameters("Name"))]`.
 #[macro_export]
-macro_rules! register_operation {
+macro_rules! register_parameter {
     ($name:expr, $constructor:expr) => {
-        ::oasgen::__private::inventory::submit!(::oasgen::__private::OperationRegister {
+        ::oasgen::__private::inventory::submit!(::oasgen::__private::ComponentRegister {
             name: $name,
-            constructor: &$constructor,
+            module_path: module_path!(),
+            constructor: &|| ::oasgen::Component::Parameter(($constructor)()),
+        });
+    };
+}
+
+/// Register a reusable response in `components.responses`.
+/// Reference it from a handler with `#[oasgen(responses(404 = "Name"))]`.
+#[macro_export]
+macro_rules! register_response {
+    ($name:expr, $constructor:expr) => {
+        ::oasgen::__private::inventory::submit!(::oasgen::__private::ComponentRegister {
+            name: $name,
+            module_path: module_path!(),
+            constructor: &|| ::oasgen::Component::Response(($constructor)()),
+        });
+    };
+}
+
+/// Register a reusable request body in `components.requestBodies`.
+/// Reference it from a handler with `#[oasgen(request_body = "Name")]`.
+#[macro_export]
+macro_rules! register_request_body {
+    ($name:expr, $constructor:expr) => {
+        ::oasgen::__private::inventory::submit!(::oasgen::__private::ComponentRegister {
+            name: $name,
+            module_path: module_path!(),
+            constructor: &|| ::oasgen::Component::RequestBody(($constructor)()),
+        });
+    };
+}
+
+/// Register a reusable header in `components.headers`.
+/// Reference it from a response with `RefOr::ref_("#/components/headers/Name")`.
+#[macro_export]
+macro_rules! register_header {
+    ($name:expr, $constructor:expr) => {
+        ::oasgen::__private::inventory::submit!(::oasgen::__private::ComponentRegister {
+            name: $name,
+            module_path: module_path!(),
+            constructor: &|| ::oasgen::Component::Header(($constructor)()),
         });
     };
 }
 
 /// Use this function if you just want the OpenAPI spec and don't need the server machinery.
 /// Note the server machinery is what registers the operations, so this schema only contains
-/// the schemas.
+/// the components.
+///
+/// If two components are registered under the same name, the one from the module path that sorts
+/// first is kept. Use `try_generate_openapi` to find out about that case.
+///
+/// The spec is plain OpenAPI 3.0: tuple structs don't carry the `x-prefixItems` marker used by the 3.1
+/// conversion, so convert it with `generate_openapi_3_1` rather than `to_openapi_3_1` to get `prefixItems`.
 pub fn generate_openapi() -> OpenAPI {
     let mut openapi = OpenAPI::default();
-    for flag in inventory::iter::<oasgen_core::SchemaRegister> {
-        let schema = (flag.constructor)();
-        openapi.schemas.insert(flag.name.to_string(), ReferenceOr::Item(schema));
-    }
-    // This is required to have stable diffing between builds
-    openapi.schemas.sort_keys();
+    insert_registered_components(&mut openapi);
+    version::strip_prefix_items(&mut openapi);
     openapi
-}
\ No newline at end of file
+}
+
+/// Like `generate_openapi`, but converted to OpenAPI 3.1. See [`to_openapi_3_1`].
+pub fn generate_openapi_3_1() -> serde_yaml::Value {
+    let mut openapi = OpenAPI::default();
+    insert_registered_components(&mut openapi);
+    to_openapi_3_1(&openapi)
+}
+
+/// Like `generate_openapi`, but returns component name collisions as errors.
+pub fn try_generate_openapi() -> Result<OpenAPI, Vec<OasgenError>> {
+    let mut openapi = OpenAPI::default();
+    let collisions = insert_registered_components(&mut openapi);
+    version::strip_prefix_items(&mut openapi);
+    if collisions.is_empty() {
+        Ok(openapi)
+    } else {
+        Err(collisions.into_iter().map(OasgenError::Invalid).collect())
+    }
+}
+
+/// Insert every schema registered with `register_schema!` (including by `#[derive(OaSchema)]`),
+/// and every component registered with `register_parameter!` and friends. Returns a diagnostic for
+/// every name that was registered more than once.
+pub(crate) fn insert_registered_components(openapi: &mut OpenAPI) -> Vec<Diagnostic> {
+    let mut collisions = Vec::new();
+    let schemas = inventory::iter::<SchemaRegister>
+        .into_iter()
+        .map(|flag| (flag.name, flag.module_path, flag));
+    for (name, flag) in dedup_registered("schemas", schemas, &mut collisions) {
+        openapi.schemas.insert(name, ReferenceOr::Item((flag.constructor)()));
+    }
+
+    let mut parameters = Vec::new();
+    let mut responses = Vec::new();
+    let mut request_bodies = Vec::new();
+    let mut headers = Vec::new();
+    for flag in inventory::iter::<ComponentRegister> {
+        match (flag.constructor)() {
+            Component::Parameter(p) => parameters.push((flag.name, flag.module_path, p)),
+            Component::Response(r) => responses.push((flag.name, flag.module_path, r)),
+            Component::RequestBody(r) => request_bodies.push((flag.name, flag.module_path, r)),
+            Component::Header(h) => headers.push((flag.name, flag.module_path, h)),
+        }
+    }
+    for (name, p) in dedup_registered("parameters", parameters.into_iter(), &mut collisions) {
+        openapi.parameters.insert(name, p);
+    }
+    for (name, r) in dedup_registered("responses", responses.into_iter(), &mut collisions) {
+        openapi.responses.insert(name, r);
+    }
+    for (name, r) in dedup_registered("requestBodies", request_bodies.into_iter(), &mut collisions) {
+        openapi.request_bodies.insert(name, r);
+    }
+    for (name, h) in dedup_registered("headers", headers.into_iter(), &mut collisions) {
+        openapi.headers.insert(name, h);
+    }
+    collisions
+}
+
+/// Key registrations by component name, sorted for stable diffing between builds.
+/// Names registered more than once are reported as collisions, and the registration from the module
+/// path that sorts first is kept, as the order `inventory` yields them in depends on link order.
+fn dedup_registered<T>(
+    kind: &'static str,
+    registered: impl Iterator<Item = (&'static str, &'static str, T)>,
+    collisions: &mut Vec<Diagnostic>,
+) -> IndexMap<String, T> {
+    let mut grouped: IndexMap<String, Vec<(&'static str, T)>> = IndexMap::new();
+    for (name, module_path, item) in registered {
+        grouped.entry(__private::component_name(name)).or_default().push((module_path, item));
+    }
+    grouped.sort_keys();
+    grouped
+        .into_iter()
+        .map(|(name, mut items)| {
+            items.sort_by_key(|(module_path, _)| *module_path);
+            if items.len() > 1 {
+                let modules = items.iter().map(|(m, _)| m.to_string()).collect::<Vec<_>>();
+                collisions.push(Diagnostic::ComponentNameCollision { kind, name: name.clone(), modules });
+            }
+            (name, items.swap_remove(0).1)
+        })
+        .collect()
+}
@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use openapiv3::OpenAPI;
+
+use crate::validate::collect_refs;
+
+pub(crate) const SCHEMA_PREFIX: &str = "#/components/schemas/";
+const COMPONENTS_PREFIX: &str = "#/components/";
+
+/// Remove every component schema, response, parameter, request body and header that isn't reachable
+/// from the paths or webhooks, following `$ref`s transitively. Schemas named in `keep` are kept
+/// regardless, along with everything they reference.
+///
+/// Other kinds of components (e.g. examples and security schemes) are left alone, and so is
+/// everything they reference.
+pub fn prune_schemas(openapi: &mut OpenAPI, keep: &[&str]) {
+    let components = &mut openapi.components;
+    let mut schemas = std::mem::take(&mut components.schemas);
+    let mut responses = std::mem::take(&mut components.responses);
+    let mut parameters = std::mem::take(&mut components.parameters);
+    let mut request_bodies = std::mem::take(&mut components.request_bodies);
+    let mut headers = std::mem::take(&mut components.headers);
+
+    let mut queue = component_refs(&*openapi);
+    queue.extend(keep.iter().map(|s| ("schemas".to_string(), s.to_string())));
+    let mut reachable = HashSet::new();
+    while let Some(component) = queue.pop() {
+        if reachable.contains(&component) {
+            continue;
+        }
+        let (kind, name) = &component;
+        queue.extend(match kind.as_str() {
+            "schemas" => schemas.get(name).map(component_refs),
+            "responses" => responses.get(name).map(component_refs),
+            "parameters" => parameters.get(name).map(component_refs),
+            "requestBodies" => request_bodies.get(name).map(component_refs),
+            "headers" => headers.get(name).map(component_refs),
+            _ => None,
+        }.unwrap_or_default());
+        reachable.insert(component);
+    }
+
+    retain_reachable(&mut schemas, "schemas", &reachable);
+    retain_reachable(&mut responses, "responses", &reachable);
+    retain_reachable(&mut parameters, "parameters", &reachable);
+    retain_reachable(&mut request_bodies, "requestBodies", &reachable);
+    retain_reachable(&mut headers, "headers", &reachable);
+    let components = &mut openapi.components;
+    components.schemas = schemas;
+    components.responses = responses;
+    components.parameters = parameters;
+    components.request_bodies = request_bodies;
+    components.headers = headers;
+}
+
+fn retain_reachable<T>(components: &mut IndexMap<String, T>, kind: &str, reachable: &HashSet<(String, String)>) {
+    components.retain(|name, _| reachable.contains(&(kind.to_string(), name.clone())));
+}
+
+/// Kind (e.g. `schemas`) and name of the components referenced anywhere in the value.
+fn component_refs<T: serde::Serialize>(value: &T) -> Vec<(String, String)> {
+    let value = serde_json::to_value(value).expect("Serializing OpenAPI spec failed.");
+    let mut refs = Vec::new();
+    collect_refs(&value, &mut String::new(), &mut refs);
+    refs.into_iter()
+        .filter_map(|(_, reference)| {
+            let mut parts = reference.strip_prefix(COMPONENTS_PREFIX)?.split('/');
+            let kind = parts.next()?;
+            let name = parts.next()?;
+            Some((kind.to_string(), name.replace("~1", "/").replace("~0", "~")))
+        })
+        .collect()
+}
+
+/// Names of the component schemas referenced anywhere in the value.
+pub(crate) fn schema_refs<T: serde::Serialize>(value: &T) -> Vec<String> {
+    component_refs(value)
+        .into_iter()
+        .filter_map(|(kind, name)| (kind == "schemas").then_some(name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openapiv3::{Operation, Parameter, RefOr, Schema, StatusCode};
+    use crate::__private::json_response;
+
+    #[test]
+    fn test_prune_schemas() {
+        let mut spec = OpenAPI::default();
+        let mut user = Schema::new_object();
+        user.properties_mut().insert("address", RefOr::schema_ref("Address"));
+        spec.schemas.insert("User", user);
+        spec.schemas.insert("Address", Schema::new_string());
+        spec.schemas.insert("Unused", Schema::new_string());
+        spec.schemas.insert("Published", Schema::new_array(RefOr::schema_ref("Item")));
+        spec.schemas.insert("Item", Schema::new_string());
+
+        let mut op = Operation::default();
+        op.add_response_success_json(Some(RefOr::schema_ref("User")));
+        spec.paths.insert_operation("/user".to_string(), http::Method::GET, op);
+
+        prune_schemas(&mut spec, &["Published"]);
+        let names = spec.schemas.keys().collect::<Vec<_>>();
+        assert_eq!(names, vec!["User", "Address", "Published", "Item"]);
+    }
+
+    #[test]
+    fn test_prune_other_components() {
+        let mut spec = OpenAPI::default();
+        spec.schemas.insert("Error", Schema::new_string());
+        spec.schemas.insert("UnusedError", Schema::new_string());
+        spec.schemas.insert("Event", Schema::new_string());
+        spec.responses.insert("NotFound".to_string(), RefOr::Item(json_response("Not found", RefOr::schema_ref("Error"))));
+        spec.responses.insert("Gone".to_string(), RefOr::Item(json_response("Gone", RefOr::schema_ref("UnusedError"))));
+        spec.parameters.insert("Page".to_string(), RefOr::Item(Parameter::query("page", Schema::new_integer())));
+
+        let mut op = Operation::default();
+        op.responses.responses.insert(StatusCode::Code(404), RefOr::ref_("#/components/responses/NotFound"));
+        spec.paths.insert_operation("/user".to_string(), http::Method::GET, op);
+        let mut webhook = Operation::default();
+        webhook.add_request_body_json(Some(RefOr::schema_ref("Event")));
+        let webhook = serde_json::to_value(webhook).unwrap();
+        spec.extensions.insert("x-webhooks".to_string(), serde_json::json!({"event": {"post": webhook}}));
+
+        prune_schemas(&mut spec, &[]);
+        assert_eq!(spec.schemas.keys().collect::<Vec<_>>(), vec!["Error", "Event"]);
+        assert_eq!(spec.responses.keys().collect::<Vec<_>>(), vec!["NotFound"]);
+        assert!(spec.parameters.is_empty());
+    }
+}
@@ -1,14 +1,15 @@
-use std::collections::HashMap;
+use std::borrow::Borrow;
 use std::env::var;
 use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
 
 use http::Method;
-use once_cell::sync::Lazy;
 use openapiv3::{OpenAPI, Operation, ReferenceOr, Parameter, ParameterKind};
 
-use oasgen_core::{OaSchema};
+use oasgen_core::{OaHandler, OaOperation, OaSchema};
+
+use crate::{Diagnostic, OasgenError, OpenApiVersion};
 
 #[cfg_attr(docsrs, doc(cfg(feature = "actix")))]
 #[cfg(feature = "actix")]
@@ -18,15 +19,6 @@ mod actix;
 mod axum;
 mod none;
 
-static OPERATION_LOOKUP: Lazy<HashMap<&'static str, &'static (dyn Fn() -> Operation + Send + Sync)>> = Lazy::new(|| {
-    let mut map = HashMap::new();
-    for flag in inventory::iter::<oasgen_core::OperationRegister> {
-        let z: &'static (dyn Fn() -> Operation + Send + Sync) = flag.constructor;
-        map.insert(flag.name, z);
-    }
-    map
-});
-
 pub struct Server<Router, Mutability = OpenAPI> {
     router: Router,
 
@@ -39,6 +31,8 @@ pub struct Server<Router, Mutability = OpenAPI> {
     pub json_route: Option<String>,
     /// Configuration to serve the spec as YAML
     pub yaml_route: Option<String>,
+    /// OpenAPI version of the served and written spec.
+    pub openapi_version: OpenApiVersion,
 
     #[cfg(feature = "swagger-ui")]
     #[cfg_attr(docsrs, doc(cfg(feature = "swagger-ui")))]
@@ -48,6 +42,15 @@ pub struct Server<Router, Mutability = OpenAPI> {
     #[cfg(feature = "swagger-ui")]
     /// Configuration for Swagger UI itself
     pub swagger_ui: Option<swagger_ui::SwaggerUi>,
+
+    /// Problems found while building the spec. Reported by `try_freeze` and `freeze`.
+    errors: Vec<OasgenError>,
+    /// Components registered under the same name. Reported by `validate`, and as errors by `try_freeze`.
+    collisions: Vec<Diagnostic>,
+    /// Whether to validate the spec when freezing.
+    validate_on_freeze: bool,
+    /// Schemas to keep when pruning. `None` if pruning is disabled.
+    prune_schemas: Option<Vec<String>>,
 }
 
 impl<Router: Clone> Clone for Server<Router, Arc<OpenAPI>> {
@@ -57,60 +60,146 @@ impl<Router: Clone> Clone for Server<Router, Arc<OpenAPI>> {
             openapi: self.openapi.clone(),
             json_route: self.json_route.clone(),
             yaml_route: self.yaml_route.clone(),
+            openapi_version: self.openapi_version,
             prefix: self.prefix.clone(),
             #[cfg(feature = "swagger-ui")]
             swagger_ui_route: self.swagger_ui_route.clone(),
             #[cfg(feature = "swagger-ui")]
             swagger_ui: self.swagger_ui.clone(),
+            errors: self.errors.clone(),
+            collisions: self.collisions.clone(),
+            validate_on_freeze: self.validate_on_freeze,
+            prune_schemas: self.prune_schemas.clone(),
         }
     }
 }
 
+impl<Router: Default> Default for Server<Router, OpenAPI> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<Router: Default> Server<Router, OpenAPI> {
     pub fn new() -> Self {
         let mut openapi = OpenAPI::default();
-        for flag in inventory::iter::<oasgen_core::SchemaRegister> {
-            let schema = (flag.constructor)();
-            openapi.schemas.insert(flag.name.to_string(), ReferenceOr::Item(schema));
-        }
-        // This is required to have stable diffing between builds
-        openapi.schemas.sort_keys();
+        let collisions = crate::insert_registered_components(&mut openapi);
         Self {
             openapi,
             router: Router::default(),
             json_route: None,
             yaml_route: None,
+            openapi_version: OpenApiVersion::default(),
             prefix: None,
             #[cfg(feature = "swagger-ui")]
             swagger_ui_route: None,
             #[cfg(feature = "swagger-ui")]
             swagger_ui: None,
+            errors: Vec::new(),
+            collisions,
+            validate_on_freeze: false,
+            prune_schemas: None,
         }
     }
 
     /// Add a handler to the OpenAPI spec (which is different than mounting it to a server).
-    fn add_handler_to_spec<F>(&mut self, path: &str, method: Method, _handler: &F) {
-        use http::Method;
+    /// Problems are recorded and reported when the server is frozen.
+    fn add_handler_to_spec<F: OaHandler<Args>, Args>(&mut self, path: &str, method: Method, _handler: &F) {
+        if let Err(e) = self.try_add_handler_to_spec::<F, Args>(path, method) {
+            self.errors.push(e);
+        }
+    }
+
+    fn try_add_handler_to_spec<F: OaHandler<Args>, Args>(&mut self, path: &str, method: Method) -> Result<(), OasgenError> {
         let item = self.openapi.paths.paths.entry(path.to_string()).or_default();
-        let item = item.as_mut().expect("Currently don't support references for PathItem");
-        let type_name = std::any::type_name::<F>();
-        let mut operation = OPERATION_LOOKUP.get(type_name)
-
-            .expect(&format!("Operation {} not found in OpenAPI spec.", type_name))();
-        modify_parameter_names(&mut operation, &path);
-        match method {
-            Method::GET => item.get = Some(operation),
-            Method::POST => item.post = Some(operation),
-            Method::PUT => item.put = Some(operation),
-            Method::DELETE => item.delete = Some(operation),
-            Method::OPTIONS => item.options = Some(operation),
-            Method::HEAD => item.head = Some(operation),
-            Method::PATCH => item.patch = Some(operation),
-            Method::TRACE => item.trace = Some(operation),
-            _ => panic!("Unsupported method: {}", method),
+        let item = item.as_mut().ok_or_else(|| OasgenError::PathItemReference {
+            path: path.to_string(),
+        })?;
+        let slot = match method {
+            Method::GET => &mut item.get,
+            Method::POST => &mut item.post,
+            Method::PUT => &mut item.put,
+            Method::DELETE => &mut item.delete,
+            Method::OPTIONS => &mut item.options,
+            Method::HEAD => &mut item.head,
+            Method::PATCH => &mut item.patch,
+            Method::TRACE => &mut item.trace,
+            _ => return Err(OasgenError::UnsupportedMethod { path: path.to_string(), method }),
+        };
+        let mut operation = handler_operation::<F, Args>();
+        modify_parameter_names(&mut operation, path);
+        *slot = Some(operation);
+        Ok(())
+    }
+
+    /// Document a webhook: a request your API sends to its consumers, rather than receives.
+    /// `handler` is only used to look up the operation, and is not mounted.
+    ///
+    /// Webhooks are emitted as `webhooks` in OpenAPI 3.1, and as the `x-webhooks` extension in 3.0.
+    pub fn webhook<F: OaHandler<Args>, Args>(mut self, name: &str, method: Method, _handler: F) -> Self {
+        if let Err(e) = self.try_add_webhook_to_spec::<F, Args>(name, method) {
+            self.errors.push(e);
+        }
+        self
+    }
+
+    fn try_add_webhook_to_spec<F: OaHandler<Args>, Args>(&mut self, name: &str, method: Method) -> Result<(), OasgenError> {
+        let operation = serde_json::to_value(handler_operation::<F, Args>()).map_err(OasgenError::json)?;
+        let webhooks = self.openapi.extensions
+            .entry("x-webhooks".to_string())
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        let item = webhooks.as_object_mut()
+            .and_then(|w| w.entry(name).or_insert_with(|| serde_json::Value::Object(Default::default())).as_object_mut())
+            .ok_or_else(|| OasgenError::WebhookReference { name: name.to_string() })?;
+        item.insert(method.as_str().to_lowercase(), operation);
+        Ok(())
+    }
+
+    /// Serve and write the spec as the given OpenAPI version. Defaults to 3.0.
+    ///
+    /// With 3.0, the frozen `openapi` is plain OpenAPI 3.0. With 3.1, tuple structs in it keep the
+    /// `x-prefixItems` marker, which `to_openapi_3_1` turns into `prefixItems`.
+    pub fn openapi_version(mut self, version: OpenApiVersion) -> Self {
+        self.openapi_version = version;
+        self
+    }
+
+    /// Problems found so far while building the spec.
+    pub fn errors(&self) -> &[OasgenError] {
+        &self.errors
+    }
+
+    /// Only emit the component schemas that are reachable from the registered operations and webhooks.
+    /// By default, every type that derives `OaSchema` in the binary is in the spec. Unreferenced
+    /// responses, parameters, request bodies and headers are removed as well.
+    ///
+    /// Pruning happens when the server is frozen (or the spec is written by
+    /// `write_and_exit_if_env_var_set`), so it sees every operation.
+    pub fn prune_schemas(mut self) -> Self {
+        self.prune_schemas.get_or_insert_with(Vec::new);
+        self
+    }
+
+    /// Keep a schema (and everything it references) when pruning, even if no operation uses it.
+    /// Implies `prune_schemas`.
+    pub fn keep_schema(mut self, name: &str) -> Self {
+        self.prune_schemas.get_or_insert_with(Vec::new).push(name.to_string());
+        self
+    }
+
+    fn prune(&mut self) {
+        if let Some(keep) = &self.prune_schemas {
+            let keep = keep.iter().map(String::as_str).collect::<Vec<_>>();
+            crate::prune_schemas(&mut self.openapi, &keep);
         }
     }
 
+    /// Also report the result of `validate` as errors when freezing the server.
+    pub fn validate_on_freeze(mut self) -> Self {
+        self.validate_on_freeze = true;
+        self
+    }
+
     /// Configure the server to add a route that serves the spec as JSON
     /// ```ignore
     /// Server::new()
@@ -150,19 +239,25 @@ impl<Router: Default> Server<Router, OpenAPI> {
     #[cfg(feature = "swagger-ui")]
     #[cfg_attr(docsrs, doc(cfg(feature = "swagger-ui")))]
     /// Specify a path to serve Swagger UI on.
+    ///
+    /// The route must end with a slash, and either `route_json_spec` or `route_yaml_spec` must
+    /// be called first. Otherwise, an error is recorded and reported when the server is frozen.
     pub fn swagger_ui(mut self, swagger_ui_route: &str) -> Self {
-        if !swagger_ui_route.ends_with('/') {
-            panic!("Swagger UI route must end with a slash. Without it, static resources will not be found.");
-        }
-        let route_without_trailing = &swagger_ui_route[..swagger_ui_route.len() - 1];
+        let Some(route_without_trailing) = swagger_ui_route.strip_suffix('/') else {
+            self.errors.push(OasgenError::SwaggerUiMissingTrailingSlash {
+                route: swagger_ui_route.to_string(),
+            });
+            return self;
+        };
+        let Some(url) = self.json_route.as_ref().or(self.yaml_route.as_ref()) else {
+            self.errors.push(OasgenError::SwaggerUiMissingSpecRoute {
+                route: swagger_ui_route.to_string(),
+            });
+            return self;
+        };
         let swagger = swagger_ui::SwaggerUi::default()
             .prefix(route_without_trailing)
-            .url(self.json_route.as_ref()
-                .or(self.yaml_route.as_ref())
-                .expect("Tried to create Swagger UI route, but no JSON or YAML route was set. \
-                On `oasgen::Server` instance, call `route_yaml_spec` or `route_json_spec`. \
-                If you manually create the route, set the field, call this method, then set the field to None.")
-            );
+            .url(url);
         self.swagger_ui_route = Some(swagger_ui_route.to_string());
         self.swagger_ui = Some(swagger);
         self
@@ -181,13 +276,17 @@ impl<Router: Default> Server<Router, OpenAPI> {
     ///
     /// This function checks the env var, and if it's found, writes the spec, and then terminates
     /// the program (with success).
-    pub fn write_and_exit_if_env_var_set<P: AsRef<Path>>(self, path: P) -> Self {
+    pub fn write_and_exit_if_env_var_set<P: AsRef<Path>>(mut self, path: P) -> Self {
         let path = path.as_ref();
         if var("OASGEN_WRITE_SPEC").map(|s| s == "1").unwrap_or(false) {
+            self.prune();
             let spec = if path.extension().map(|e| e == "json").unwrap_or(false) {
-                serde_json::to_string(&self.openapi).expect("Serializing OpenAPI spec to JSON failed.")
+                crate::version::to_json(&self.openapi, self.openapi_version)
+                    .expect("Serializing OpenAPI spec to JSON failed.")
             } else {
-                serde_yaml::to_string(&self.openapi).expect("Serializing OpenAPI spec failed.")
+                crate::version::to_yaml(&self.openapi, self.openapi_version)
+                    .map(String::into_bytes)
+                    .expect("Serializing OpenAPI spec failed.")
             };
             std::fs::write(path, spec).expect("Writing OpenAPI spec to file failed.");
             eprintln!("{}: Wrote OpenAPI spec.", path.display());
@@ -199,19 +298,99 @@ impl<Router: Default> Server<Router, OpenAPI> {
     ///
     /// Functionally, it moves the OpenAPI spec into an Arc, so that view handlers (which are async
     /// and therefore have undetermined lifespans) can hold onto it.
+    ///
+    /// Panics if any problems were found while building the spec. Use `try_freeze` to handle them.
+    /// Component name collisions are only errors with `validate_on_freeze`; otherwise, use `validate`
+    /// to find them.
     pub fn freeze(self) -> Server<Router, Arc<OpenAPI>> {
-        Server {
+        let collisions_are_errors = self.validate_on_freeze;
+        match self.finish(collisions_are_errors) {
+            Ok(server) => server,
+            Err(errors) => {
+                let errors = errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+                panic!("Failed to build OpenAPI spec:\n{}", errors.join("\n"))
+            }
+        }
+    }
+
+    /// Like `freeze`, but returns every problem found while building the spec instead of panicking,
+    /// including component name collisions.
+    pub fn try_freeze(self) -> Result<Server<Router, Arc<OpenAPI>>, Vec<OasgenError>> {
+        self.finish(true)
+    }
+
+    fn finish(mut self, collisions_are_errors: bool) -> Result<Server<Router, Arc<OpenAPI>>, Vec<OasgenError>> {
+        self.prune();
+        // The marker is only needed to convert the spec to 3.1 when it's served.
+        if self.openapi_version == OpenApiVersion::V3_0 {
+            crate::version::strip_prefix_items(&mut self.openapi);
+        }
+        if self.validate_on_freeze {
+            // Includes the collisions.
+            let diagnostics = self.validate();
+            self.errors.extend(diagnostics.into_iter().map(OasgenError::Invalid));
+        } else if collisions_are_errors {
+            self.errors.extend(self.collisions.iter().cloned().map(OasgenError::Invalid));
+        }
+        if !self.errors.is_empty() {
+            return Err(self.errors);
+        }
+        Ok(Server {
             router: self.router,
             openapi: Arc::new(self.openapi),
             json_route: self.json_route,
             yaml_route: self.yaml_route,
+            openapi_version: self.openapi_version,
             prefix: self.prefix,
             #[cfg(feature = "swagger-ui")]
             swagger_ui_route: self.swagger_ui_route,
             #[cfg(feature = "swagger-ui")]
             swagger_ui: self.swagger_ui,
+            errors: Vec::new(),
+            collisions: self.collisions,
+            validate_on_freeze: false,
+            prune_schemas: None,
+        })
+    }
+}
+
+impl<Router, Mutability: Borrow<OpenAPI>> Server<Router, Mutability> {
+    /// Check the spec for component name collisions, dangling `$ref`s, mismatched path parameters,
+    /// and duplicate `operationId`s. See [`crate::validate`].
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = self.collisions.clone();
+        diagnostics.extend(crate::validate(self.openapi.borrow()));
+        diagnostics
+    }
+}
+
+/// The operation of a handler. Without an `operationId`, e.g. for a standalone `#[oasgen]` fn, the id is
+/// the handler's path, so an associated fn `Users::list` gets `Users_list`.
+fn handler_operation<F: OaHandler<Args>, Args>() -> Operation {
+    let mut operation = F::Operation::operation();
+    if operation.operation_id.is_none() {
+        operation.operation_id = handler_op_id(std::any::type_name::<F>());
+    }
+    operation
+}
+
+/// The `operationId` for the type name of a fn item, without the crate name and generic arguments.
+/// Closures and fn pointers have no path to name the operation with.
+fn handler_op_id(type_name: &str) -> Option<String> {
+    if type_name.starts_with("fn(") || type_name.contains("{{") {
+        return None;
+    }
+    let mut path = String::new();
+    let mut depth = 0usize;
+    for c in type_name.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => path.push(c),
+            _ => {}
         }
     }
+    crate::__private::fn_path_to_op_id(&path)
 }
 
 // Note: this takes an OpenAPI url, which parameterizes like: /path/{param}
@@ -219,9 +398,7 @@ fn modify_parameter_names(operation: &mut Operation, path: &str) {
     if !path.contains("{") {
         return;
     }
-    let path_parts = path.split("/")
-        .filter(|part| part.starts_with("{"))
-        .map(|part| &part[1..part.len() - 1]);
+    let path_parts = crate::validate::path_template_names(path);
     let path_params = operation.parameters.iter_mut()
         .filter_map(|mut p| p.as_mut())
         .filter(|p| matches!(p.kind, ParameterKind::Path { .. }));
@@ -246,4 +423,78 @@ mod tests {
         assert_eq!(operation.parameters[0].as_item().unwrap().name, "id", "path param name is updated");
         assert_eq!(operation.parameters[1].as_item().unwrap().name, "query", "leave query param alone");
     }
+
+    #[test]
+    fn test_modify_parameter_names_within_segment() {
+        let path = "/api/v1/{owner}-{repo}/files/{name}.json";
+        let mut operation = Operation::default();
+        for name in ["a", "b", "c"] {
+            operation.parameters.push(Parameter::path(name, oa::Schema::new_string()).into());
+        }
+        modify_parameter_names(&mut operation, path);
+        let names = operation.parameters.iter().map(|p| p.as_item().unwrap().name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["owner", "repo", "name"]);
+    }
+
+    #[test]
+    fn test_handler_op_id() {
+        assert_eq!(handler_op_id("app::handlers::Users::list").as_deref(), Some("handlers_Users_list"));
+        assert_eq!(handler_op_id("app::create<app::Task>").as_deref(), Some("create"));
+        assert_eq!(handler_op_id("app::Page<app::Task>::list").as_deref(), Some("Page_list"));
+        assert_eq!(handler_op_id("app::main::{{closure}}"), None);
+        assert_eq!(handler_op_id("fn() -> u32"), None);
+    }
+
+    struct Registered;
+
+    impl OaOperation for Registered {
+        fn operation() -> Operation {
+            Operation::default()
+        }
+    }
+
+    fn registered() -> oasgen_core::Documented<Registered, ()> {
+        oasgen_core::Documented::new(Registered, ())
+    }
+
+    #[test]
+    fn test_errors_are_accumulated() {
+        let mut server = Server::none().get("/a", registered);
+        server.openapi.paths.paths.insert("/b".to_string(), ReferenceOr::ref_("#/paths/~1a"));
+        server.add_handler_to_spec("/a", Method::CONNECT, &registered);
+        server.add_handler_to_spec("/b", Method::GET, &registered);
+        server.add_handler_to_spec("/a", Method::from_bytes(b"LINK").unwrap(), &registered);
+        let errors = server.try_freeze().err().expect("errors are reported at freeze");
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(&errors[0], OasgenError::UnsupportedMethod { method, .. } if method == Method::CONNECT));
+        assert!(matches!(&errors[1], OasgenError::PathItemReference { path } if path == "/b"));
+        assert!(matches!(&errors[2], OasgenError::UnsupportedMethod { path, .. } if path == "/a"));
+    }
+
+    #[test]
+    fn test_validate_on_freeze() {
+        let server = Server::none().get("/tasks/{id}", registered);
+        assert_eq!(server.validate().len(), 1);
+        assert!(server.try_freeze().is_ok(), "validation is opt-in");
+
+        let errors = Server::none()
+            .get("/tasks/{id}", registered)
+            .validate_on_freeze()
+            .try_freeze()
+            .err()
+            .expect("validation errors are reported at freeze");
+        assert_eq!(errors, vec![OasgenError::Invalid(Diagnostic::MissingPathParameter {
+            operation: "GET /tasks/{id}".to_string(),
+            name: "id".to_string(),
+        })]);
+    }
+
+    #[test]
+    fn test_prune_schemas_on_freeze() {
+        let mut server = Server::none().get("/a", registered).keep_schema("Kept");
+        server.openapi.schemas.insert("Kept", oa::Schema::new_string());
+        server.openapi.schemas.insert("Unused", oa::Schema::new_string());
+        let server = server.freeze();
+        assert_eq!(server.openapi.schemas.keys().collect::<Vec<_>>(), vec!["Kept"]);
+    }
 }
@@ -7,11 +7,11 @@ use futures::future::{ok, Ready};
 use openapiv3::OpenAPI;
 use std::sync::Arc;
 use actix_web::http::header::CONTENT_TYPE;
-use crate::Format;
+use crate::{Format, OpenApiVersion};
 
 use super::Server;
 
-use oasgen_core::{OaParameter, OaSchema};
+use oasgen_core::{OaHandler, OaParameter, OaSchema};
 
 #[derive(Default)]
 pub struct ActixRouter(Vec<InnerResourceFactory<'static>>);
@@ -65,7 +65,7 @@ impl Server<ActixRouter> {
 
     pub fn get<F, Args>(mut self, path: &str, handler: F) -> Self
     where
-        F: Handler<Args>,
+        F: Handler<Args> + OaHandler<Args>,
         Args: FromRequest + 'static,
         F::Output: Responder + 'static,
         <F as Handler<Args>>::Output: OaParameter,
@@ -80,7 +80,7 @@ impl Server<ActixRouter> {
 
     pub fn post<F, Args>(mut self, path: &str, handler: F) -> Self
     where
-        F: Handler<Args> + Copy + Send,
+        F: Handler<Args> + OaHandler<Args> + Copy + Send,
         Args: FromRequest + 'static,
         F::Output: Responder + 'static,
         <F as Handler<Args>>::Output: OaParameter,
@@ -96,7 +96,7 @@ impl Server<ActixRouter> {
 
     pub fn patch<F, Args>(mut self, path: &str, handler: F) -> Self
     where
-        F: Handler<Args> + Copy + Send,
+        F: Handler<Args> + OaHandler<Args> + Copy + Send,
         Args: FromRequest + 'static,
         F::Output: Responder + 'static,
         <F as Handler<Args>>::Output: OaParameter,
@@ -119,18 +119,16 @@ impl Server<ActixRouter, Arc<OpenAPI>> {
         }
         if let Some(path) = self.json_route {
             scope = scope.service(
-                web::resource(&path).route(web::get().to(OaSpecJsonHandler(self.openapi.clone()))),
+                web::resource(&path).route(web::get().to(OaSpecJsonHandler(self.openapi.clone(), self.openapi_version))),
             );
         }
         if let Some(path) = self.yaml_route {
             scope = scope.service(
-                web::resource(&path).route(web::get().to(OaSpecYamlHandler(self.openapi.clone()))),
+                web::resource(&path).route(web::get().to(OaSpecYamlHandler(self.openapi.clone(), self.openapi_version))),
             );
         }
         #[cfg(feature = "swagger-ui")]
-        if self.swagger_ui_route.is_some() && self.swagger_ui.is_some() {
-            let path = self.swagger_ui_route.unwrap();
-            let swagger_ui = self.swagger_ui.unwrap();
+        if let (Some(path), Some(swagger_ui)) = (self.swagger_ui_route, self.swagger_ui) {
             let path = format!("{}{{tail:.*}}", path);
             scope = scope.app_data(web::Data::new(swagger_ui));
             scope = scope.service(web::resource(path).route(web::get().to(handler_swagger)));
@@ -140,26 +138,35 @@ impl Server<ActixRouter, Arc<OpenAPI>> {
 }
 
 #[derive(Clone)]
-struct OaSpecJsonHandler(Arc<OpenAPI>);
+struct OaSpecJsonHandler(Arc<OpenAPI>, OpenApiVersion);
 
 impl Handler<()> for OaSpecJsonHandler {
     type Output = Result<HttpResponse, Error>;
     type Future = Ready<Self::Output>;
 
     fn call(&self, _: ()) -> Self::Future {
-        ok(HttpResponse::Ok().json(&*self.0))
+        let json = match crate::version::to_json(&self.0, self.1) {
+            Ok(json) => json,
+            Err(e) => return ok(HttpResponse::InternalServerError().body(e.to_string())),
+        };
+        ok(HttpResponse::Ok()
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .body(json))
     }
 }
 
 #[derive(Clone)]
-struct OaSpecYamlHandler(Arc<OpenAPI>);
+struct OaSpecYamlHandler(Arc<OpenAPI>, OpenApiVersion);
 
 impl Handler<()> for OaSpecYamlHandler {
     type Output = Result<HttpResponse, Error>;
     type Future = Ready<Self::Output>;
 
     fn call(&self, _: ()) -> Self::Future {
-        let yaml = serde_yaml::to_string(&*self.0).unwrap();
+        let yaml = match crate::version::to_yaml(&self.0, self.1) {
+            Ok(yaml) => yaml,
+            Err(e) => return ok(HttpResponse::InternalServerError().body(e.to_string())),
+        };
         ok(HttpResponse::Ok()
             .insert_header((CONTENT_TYPE, "text/yaml"))
             .body(yaml))
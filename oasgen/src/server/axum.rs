@@ -12,6 +12,7 @@ use openapiv3::OpenAPI;
 use http_body_util::{BodyExt, Full};
 
 use super::Server;
+use crate::{OaHandler, OasgenError};
 
 pub struct Router<S>(IndexMap<String, MethodRouter<S>>);
 
@@ -40,9 +41,9 @@ impl<S> Server<Router<S>, OpenAPI>
         }
     }
 
-    pub fn get<F, T>(mut self, path: &str, handler: F) -> Self
+    pub fn get<F, T, Args>(mut self, path: &str, handler: F) -> Self
         where
-            F: Handler<T, S>,
+            F: Handler<T, S> + OaHandler<Args>,
             T: 'static,
             F: Copy + Send,
     {
@@ -51,9 +52,9 @@ impl<S> Server<Router<S>, OpenAPI>
         self
     }
 
-    pub fn post<F, T>(mut self, path: &str, handler: F) -> Self
+    pub fn post<F, T, Args>(mut self, path: &str, handler: F) -> Self
         where
-            F: Handler<T, S>,
+            F: Handler<T, S> + OaHandler<Args>,
             T: 'static,
             F: Copy + Send,
     {
@@ -62,9 +63,9 @@ impl<S> Server<Router<S>, OpenAPI>
         self
     }
 
-    pub fn put<F, T>(mut self, path: &str, handler: F) -> Self
+    pub fn put<F, T, Args>(mut self, path: &str, handler: F) -> Self
         where
-            F: Handler<T, S>,
+            F: Handler<T, S> + OaHandler<Args>,
             T: 'static,
             F: Copy + Send,
     {
@@ -73,9 +74,9 @@ impl<S> Server<Router<S>, OpenAPI>
         self
     }
 
-    pub fn patch<F, T>(mut self, path: &str, handler: F) -> Self
+    pub fn patch<F, T, Args>(mut self, path: &str, handler: F) -> Self
         where
-            F: Handler<T, S>,
+            F: Handler<T, S> + OaHandler<Args>,
             T: 'static,
             F: Copy + Send,
     {
@@ -84,9 +85,9 @@ impl<S> Server<Router<S>, OpenAPI>
         self
     }
 
-    pub fn delete<F, T>(mut self, path: &str, handler: F) -> Self
+    pub fn delete<F, T, Args>(mut self, path: &str, handler: F) -> Self
         where
-            F: Handler<T, S>,
+            F: Handler<T, S> + OaHandler<Args>,
             T: 'static,
             F: Copy + Send,
     {
@@ -99,7 +100,12 @@ impl<S> Server<Router<S>, OpenAPI>
 impl<S> Server<Router<S>, Arc<OpenAPI>>
     where
         S: Clone + Send + Sync + 'static {
+    /// Panics if the spec can't be serialized. Use `try_into_router` to handle the error.
     pub fn into_router(self) -> axum::Router<S> {
+        self.try_into_router().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_into_router(self) -> Result<axum::Router<S>, OasgenError> {
         use axum::response::IntoResponse;
 
         let mut router = axum::Router::new();
@@ -109,8 +115,8 @@ impl<S> Server<Router<S>, Arc<OpenAPI>>
 
         if let Some(json_route) = &self.json_route {
             let spec = self.openapi.as_ref();
-            let bytes = serde_json::to_vec(spec).unwrap();
-            router = router.route(&json_route, routing::get(|| async {
+            let bytes = crate::version::to_json(spec, self.openapi_version)?;
+            router = router.route(json_route, routing::get(|| async {
                 (
                     [(
                         http::header::CONTENT_TYPE,
@@ -123,8 +129,8 @@ impl<S> Server<Router<S>, Arc<OpenAPI>>
 
         if let Some(yaml_route) = &self.yaml_route {
             let spec = self.openapi.as_ref();
-            let yaml = serde_yaml::to_string(spec).unwrap();
-            router = router.route(&yaml_route, routing::get(|| async {
+            let yaml = crate::version::to_yaml(spec, self.openapi_version)?;
+            router = router.route(yaml_route, routing::get(|| async {
                 (
                     [(
                         http::header::CONTENT_TYPE,
@@ -154,10 +160,10 @@ impl<S> Server<Router<S>, Arc<OpenAPI>>
                 }
             });
             router = router
-                .route(&format!("{}", &path), handler.clone());
+                .route(&path, handler.clone());
             router = router
                 .route(&format!("{}{{*rest}}", &path), handler)
         }
-        router
+        Ok(router)
     }
 }
@@ -1,5 +1,5 @@
 use http::Method;
-use oasgen_core::{OaSchema};
+use oasgen_core::{OaHandler, OaSchema};
 use crate::Server;
 
 
@@ -8,12 +8,12 @@ impl Server<()> {
         Self::new()
     }
 
-    pub fn get<F>(mut self, path: &str, handler: F) -> Self {
+    pub fn get<F: OaHandler<Args>, Args>(mut self, path: &str, handler: F) -> Self {
         self.add_handler_to_spec(path, Method::GET, &handler);
         self
     }
 
-    pub fn post<F>(mut self, path: &str, handler: F) -> Self {
+    pub fn post<F: OaHandler<Args>, Args>(mut self, path: &str, handler: F) -> Self {
         self.add_handler_to_spec(path, Method::POST, &handler);
         self
     }
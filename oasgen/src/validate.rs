@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use openapiv3::{OpenAPI, ParameterKind};
+use serde_json::Value;
+
+/// A consistency problem found in a generated spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// A `$ref` points at something that isn't in the spec. Usually a type that implements
+    /// `OaSchema` with `schema_ref`, but was never registered with `register_schema!`.
+    DanglingReference { location: String, reference: String },
+    /// The path template contains `{name}`, but the operation declares no path parameter for it.
+    MissingPathParameter { operation: String, name: String },
+    /// The operation declares a path parameter that doesn't appear in the path template.
+    UnknownPathParameter { operation: String, name: String },
+    /// More than one operation uses the same `operationId`.
+    DuplicateOperationId { operation_id: String, operations: Vec<String> },
+    /// More than one component of the same kind (e.g. `schemas`) was registered under the same
+    /// name, so only the first one is in the spec. For derived schemas, rename one of them with
+    /// `#[oasgen(rename = "...")]` or `#[oasgen(qualified_name)]`.
+    ComponentNameCollision { kind: &'static str, name: String, modules: Vec<String> },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::DanglingReference { location, reference } => {
+                write!(f, "{location}: Reference {reference} not found in OpenAPI spec.")
+            }
+            Diagnostic::MissingPathParameter { operation, name } => {
+                write!(f, "{operation}: Path parameter {{{name}}} is not declared by the operation.")
+            }
+            Diagnostic::UnknownPathParameter { operation, name } => {
+                write!(f, "{operation}: Path parameter {name} is declared, but not in the path template.")
+            }
+            Diagnostic::DuplicateOperationId { operation_id, operations } => {
+                write!(f, "operationId {operation_id} is used by multiple operations: {}", operations.join(", "))
+            }
+            Diagnostic::ComponentNameCollision { kind, name, modules } => write!(
+                f,
+                "#/components/{kind}/{name} is registered multiple times, in modules: {}. \
+                Use #[oasgen(rename = \"...\")] or #[oasgen(qualified_name)] to give them distinct names.",
+                modules.join(", ")
+            ),
+        }
+    }
+}
+
+/// Check a spec for dangling `$ref`s, mismatched path parameters, and duplicate `operationId`s.
+/// Webhooks (`x-webhooks`) are checked for references and `operationId`s; they have no path templates.
+pub fn validate(openapi: &OpenAPI) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    dangling_references(openapi, &mut diagnostics);
+    path_parameters(openapi, &mut diagnostics);
+    duplicate_operation_ids(openapi, &mut diagnostics);
+    diagnostics
+}
+
+fn dangling_references(openapi: &OpenAPI, diagnostics: &mut Vec<Diagnostic>) {
+    let spec = serde_json::to_value(openapi).expect("Serializing OpenAPI spec failed.");
+    let mut refs = Vec::new();
+    collect_refs(&spec, &mut String::from("#"), &mut refs);
+    for (location, reference) in refs {
+        // External references can't be checked here.
+        let Some(pointer) = reference.strip_prefix('#') else {
+            continue;
+        };
+        if spec.pointer(pointer).is_none() {
+            diagnostics.push(Diagnostic::DanglingReference { location, reference });
+        }
+    }
+}
+
+/// Collect every `$ref` in the value, along with the JSON pointer to where it was found.
+pub(crate) fn collect_refs(value: &Value, location: &mut String, refs: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                refs.push((location.clone(), reference.clone()));
+            }
+            for (key, value) in map {
+                let len = location.len();
+                location.push('/');
+                location.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                collect_refs(value, location, refs);
+                location.truncate(len);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                let len = location.len();
+                location.push_str(&format!("/{i}"));
+                collect_refs(value, location, refs);
+                location.truncate(len);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The names of the `{name}` parameters in a path template, in order. A segment can hold more than one,
+/// e.g. `/v1/{a}-{b}`, or text around one, e.g. `/files/{name}.json`. Router syntax is stripped from the
+/// name: actix's regex suffix, e.g. `{id:\d+}`, and axum's wildcard prefix, e.g. `{*rest}`.
+pub(crate) fn path_template_names(path: &str) -> impl Iterator<Item = &str> {
+    let mut rest = path;
+    std::iter::from_fn(move || {
+        let start = rest.find('{')?;
+        // Regexes can contain braces of their own, e.g. `{id:\d{3}}`.
+        let mut depth = 0;
+        let len = rest[start..].find(|c| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            depth == 0
+        })?;
+        let param = &rest[start + 1..start + len];
+        rest = &rest[start + len + 1..];
+        let name = param.split_once(':').map_or(param, |(name, _regex)| name);
+        Some(name.strip_prefix('*').unwrap_or(name))
+    })
+}
+
+fn path_parameters(openapi: &OpenAPI, diagnostics: &mut Vec<Diagnostic>) {
+    for (path, method, operation, item) in openapi.operations() {
+        let operation_name = format!("{} {}", method.to_uppercase(), path);
+        let template = path_template_names(path).collect::<Vec<_>>();
+        let declared = item.parameters.iter()
+            .chain(operation.parameters.iter())
+            // Unresolvable references are reported as dangling.
+            .filter_map(|p| p.resolve(openapi).ok())
+            .filter(|p| matches!(p.kind, ParameterKind::Path { .. }))
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>();
+        for name in &template {
+            if !declared.contains(name) {
+                diagnostics.push(Diagnostic::MissingPathParameter {
+                    operation: operation_name.clone(),
+                    name: name.to_string(),
+                });
+            }
+        }
+        for name in &declared {
+            if !template.contains(name) {
+                diagnostics.push(Diagnostic::UnknownPathParameter {
+                    operation: operation_name.clone(),
+                    name: name.to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn duplicate_operation_ids(openapi: &OpenAPI, diagnostics: &mut Vec<Diagnostic>) {
+    let mut seen: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (path, method, operation, _) in openapi.operations() {
+        if let Some(id) = &operation.operation_id {
+            seen.entry(id).or_default().push(format!("{} {}", method.to_uppercase(), path));
+        }
+    }
+    let webhooks = openapi.extensions.get("x-webhooks").and_then(Value::as_object).into_iter().flatten();
+    for (name, item) in webhooks {
+        for (method, operation) in item.as_object().into_iter().flatten() {
+            if let Some(id) = operation.get("operationId").and_then(Value::as_str) {
+                seen.entry(id).or_default().push(format!("webhook {} {}", method.to_uppercase(), name));
+            }
+        }
+    }
+    for (operation_id, operations) in seen {
+        if operations.len() > 1 {
+            diagnostics.push(Diagnostic::DuplicateOperationId {
+                operation_id: operation_id.to_string(),
+                operations,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openapiv3::{Operation, Parameter, RefOr, Schema};
+
+    fn operation(id: &str, parameters: Vec<Parameter>) -> Operation {
+        Operation {
+            operation_id: Some(id.to_string()),
+            parameters: parameters.into_iter().map(RefOr::Item).collect(),
+            ..Operation::default()
+        }
+    }
+
+    #[test]
+    fn test_dangling_reference() {
+        let mut spec = OpenAPI::default();
+        let mut user = Schema::new_object();
+        user.properties_mut().insert("address", RefOr::schema_ref("Address"));
+        user.properties_mut().insert("name", RefOr::schema_ref("Name"));
+        spec.schemas.insert("User", user);
+        spec.schemas.insert("Name", Schema::new_string());
+        assert_eq!(validate(&spec), vec![Diagnostic::DanglingReference {
+            location: "#/components/schemas/User/properties/address".to_string(),
+            reference: "#/components/schemas/Address".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_path_parameters() {
+        let mut spec = OpenAPI::default();
+        let op = operation("get_task", vec![Parameter::path("task", Schema::new_integer())]);
+        spec.paths.insert_operation("/tasks/{id}".to_string(), http::Method::GET, op);
+        assert_eq!(validate(&spec), vec![
+            Diagnostic::MissingPathParameter { operation: "GET /tasks/{id}".to_string(), name: "id".to_string() },
+            Diagnostic::UnknownPathParameter { operation: "GET /tasks/{id}".to_string(), name: "task".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_duplicate_webhook_operation_id() {
+        let mut spec = OpenAPI::default();
+        spec.paths.insert_operation("/events".to_string(), http::Method::GET, operation("event", vec![]));
+        let webhook = serde_json::to_value(operation("event", vec![])).unwrap();
+        spec.extensions.insert("x-webhooks".to_string(), serde_json::json!({"event": {"post": webhook}}));
+        assert_eq!(validate(&spec), vec![Diagnostic::DuplicateOperationId {
+            operation_id: "event".to_string(),
+            operations: vec!["GET /events".to_string(), "webhook POST event".to_string()],
+        }]);
+    }
+
+    #[test]
+    fn test_path_template_names() {
+        assert_eq!(path_template_names("/files/{name}.json").collect::<Vec<_>>(), vec!["name"]);
+        assert_eq!(path_template_names("/v1/{a}-{b}/{c}").collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        assert_eq!(path_template_names("/tasks").count(), 0);
+        assert_eq!(path_template_names("/users/{id:\\d+}/{code:\\w{3}}").collect::<Vec<_>>(), vec!["id", "code"]);
+        assert_eq!(path_template_names("/static/{*rest}").collect::<Vec<_>>(), vec!["rest"]);
+    }
+
+    #[test]
+    fn test_duplicate_operation_id() {
+        let mut spec = OpenAPI::default();
+        spec.paths.insert_operation("/a".to_string(), http::Method::GET, operation("list", vec![]));
+        spec.paths.insert_operation("/b".to_string(), http::Method::GET, operation("list", vec![]));
+        assert_eq!(validate(&spec), vec![Diagnostic::DuplicateOperationId {
+            operation_id: "list".to_string(),
+            operations: vec!["GET /a".to_string(), "GET /b".to_string()],
+        }]);
+    }
+}
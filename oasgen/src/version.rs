@@ -0,0 +1,356 @@
+use oasgen_core::{KEY_SCHEMA_EXTENSION, PREFIX_ITEMS_EXTENSION};
+use openapiv3::{OpenAPI, RefOr, Schema};
+use serde_yaml::{Mapping, Value};
+
+use crate::OasgenError;
+
+/// The OpenAPI version to emit.
+///
+/// The spec is always built as OpenAPI 3.0. For 3.1, it's converted when it's serialized,
+/// see [`to_openapi_3_1`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpenApiVersion {
+    #[default]
+    V3_0,
+    V3_1,
+}
+
+/// Keywords that describe a schema rather than constrain it. They stay on the outer schema when
+/// a nullable schema is wrapped in `anyOf`.
+const ANNOTATIONS: &[&str] = &[
+    "title",
+    "description",
+    "default",
+    "examples",
+    "deprecated",
+    "readOnly",
+    "writeOnly",
+    "externalDocs",
+];
+
+/// Convert a spec to OpenAPI 3.1, whose schemas are JSON Schema 2020-12:
+///
+/// - `nullable: true` becomes a `type` array with `"null"`, or an `anyOf` with `{type: "null"}`.
+/// - `example` becomes `examples`, and single value `enum`s become `const`.
+/// - `allOf` wrapping a single `$ref` becomes a `$ref` with sibling keywords.
+/// - Boolean `exclusiveMinimum` and `exclusiveMaximum` become numbers.
+/// - Tuple structs (marked with `x-prefixItems`) get `prefixItems`, from the `anyOf` of their `items`.
+/// - `x-key-schema` (emitted for maps with constrained keys) becomes `propertyNames`.
+/// - `x-webhooks` (added by `Server::webhook`) becomes `webhooks`.
+///
+/// The result is a `serde_yaml::Value` because its mappings keep their keys in order (serde_json's
+/// only do with the `preserve_order` feature). Serialize it with `serde_json` for JSON.
+pub fn to_openapi_3_1(openapi: &OpenAPI) -> Value {
+    let mut spec = serde_yaml::to_value(openapi).expect("Serializing OpenAPI spec failed.");
+    let Some(root) = spec.as_mapping_mut() else {
+        return spec;
+    };
+    root.insert("openapi".into(), "3.1.0".into());
+    if let Some(webhooks) = root.shift_remove("x-webhooks") {
+        root.insert("webhooks".into(), webhooks);
+    }
+    for (key, value) in root.iter_mut() {
+        if key == "components" {
+            convert_components(value);
+        } else {
+            convert_schemas_in(value);
+        }
+    }
+    spec
+}
+
+/// The spec as written for OpenAPI 3.0, without the `x-prefixItems` marker used by the 3.1 conversion.
+fn to_openapi_3_0(openapi: &OpenAPI) -> Value {
+    let mut spec = serde_yaml::to_value(openapi).expect("Serializing OpenAPI spec failed.");
+    convert_extensions_3_0(&mut spec);
+    spec
+}
+
+fn convert_extensions_3_0(value: &mut Value) {
+    match value {
+        Value::Mapping(map) => {
+            map.shift_remove(PREFIX_ITEMS_EXTENSION);
+            for (key, value) in map.iter_mut() {
+                match key.as_str() {
+                    Some(KEY_SCHEMA_EXTENSION) => restore_order(value),
+                    // Examples are user data, not spec.
+                    Some("example" | "examples") => {}
+                    _ => convert_extensions_3_0(value),
+                }
+            }
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(convert_extensions_3_0),
+        _ => {}
+    }
+}
+
+/// Remove the `x-prefixItems` marker from a spec that won't be converted to 3.1, so that it's plain OpenAPI 3.0.
+pub(crate) fn strip_prefix_items(openapi: &mut OpenAPI) {
+    let mut spec = serde_json::to_value(&*openapi).expect("Serializing OpenAPI spec failed.");
+    if strip_prefix_items_in(&mut spec) {
+        *openapi = serde_json::from_value(spec).expect("Deserializing OpenAPI spec failed.");
+    }
+}
+
+/// Returns whether any marker was removed.
+fn strip_prefix_items_in(value: &mut serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut stripped = map.remove(PREFIX_ITEMS_EXTENSION).is_some();
+            for (key, value) in map.iter_mut() {
+                // Examples are user data, not spec.
+                if key != "example" && key != "examples" {
+                    stripped |= strip_prefix_items_in(value);
+                }
+            }
+            stripped
+        }
+        serde_json::Value::Array(items) => items.iter_mut().fold(false, |stripped, v| strip_prefix_items_in(v) | stripped),
+        _ => false,
+    }
+}
+
+/// Extensions are stored as `serde_json::Value`, whose keys are sorted unless serde_json's
+/// `preserve_order` feature is enabled. Round trip a schema stored in an extension through
+/// `Schema` to put its keys back in their usual order.
+fn restore_order(schema: &mut Value) {
+    if let Ok(typed) = serde_yaml::from_value::<RefOr<Schema>>(schema.clone()) {
+        *schema = serde_yaml::to_value(typed).expect("Serializing schema failed.");
+    }
+}
+
+pub(crate) fn to_json(openapi: &OpenAPI, version: OpenApiVersion) -> Result<Vec<u8>, OasgenError> {
+    match version {
+        OpenApiVersion::V3_0 => serde_json::to_vec(&to_openapi_3_0(openapi)),
+        OpenApiVersion::V3_1 => serde_json::to_vec(&to_openapi_3_1(openapi)),
+    }
+    .map_err(OasgenError::json)
+}
+
+pub(crate) fn to_yaml(openapi: &OpenAPI, version: OpenApiVersion) -> Result<String, OasgenError> {
+    match version {
+        OpenApiVersion::V3_0 => serde_yaml::to_string(&to_openapi_3_0(openapi)),
+        OpenApiVersion::V3_1 => serde_yaml::to_string(&to_openapi_3_1(openapi)),
+    }
+    .map_err(OasgenError::yaml)
+}
+
+/// `{type: "null"}`
+pub(crate) fn null_type() -> Value {
+    let mut map = Mapping::new();
+    map.insert("type".into(), "null".into());
+    Value::Mapping(map)
+}
+
+fn convert_components(components: &mut Value) {
+    let Some(components) = components.as_mapping_mut() else {
+        return;
+    };
+    for (key, value) in components.iter_mut() {
+        if key == "schemas" {
+            if let Some(schemas) = value.as_mapping_mut() {
+                schemas.values_mut().for_each(convert_schema);
+            }
+        } else {
+            convert_schemas_in(value);
+        }
+    }
+}
+
+/// Convert every schema under `schema` keys, e.g. in parameters and media types.
+fn convert_schemas_in(value: &mut Value) {
+    match value {
+        Value::Mapping(map) => {
+            for (key, value) in map.iter_mut() {
+                match key.as_str() {
+                    Some("schema") => convert_schema(value),
+                    // Examples are user data, not spec.
+                    Some("example" | "examples") => {}
+                    _ => convert_schemas_in(value),
+                }
+            }
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(convert_schemas_in),
+        _ => {}
+    }
+}
+
+pub(crate) fn convert_schema(schema: &mut Value) {
+    let Some(map) = schema.as_mapping_mut() else {
+        return;
+    };
+    if let Some(key_schema) = map.get_mut(KEY_SCHEMA_EXTENSION) {
+        restore_order(key_schema);
+    }
+    for key in ["items", "additionalProperties", "not", KEY_SCHEMA_EXTENSION] {
+        if let Some(value) = map.get_mut(key) {
+            convert_schema(value);
+        }
+    }
+    if let Some(Value::Mapping(properties)) = map.get_mut("properties") {
+        properties.values_mut().for_each(convert_schema);
+    }
+    for key in ["allOf", "anyOf", "oneOf"] {
+        if let Some(Value::Sequence(schemas)) = map.get_mut(key) {
+            schemas.iter_mut().for_each(convert_schema);
+        }
+    }
+
+    if map.shift_remove(PREFIX_ITEMS_EXTENSION).is_some()
+        && let Some(Value::Mapping(mut items)) = map.shift_remove("items")
+        && let Some(prefix_items) = items.shift_remove("anyOf")
+    {
+        map.insert("prefixItems".into(), prefix_items);
+    }
+    if let Some(key_schema) = map.shift_remove(KEY_SCHEMA_EXTENSION) {
+        map.insert("propertyNames".into(), key_schema);
+    }
+    if let Some(example) = map.shift_remove("example") {
+        map.insert("examples".into(), Value::Sequence(vec![example]));
+    }
+    for (exclusive, bound) in [("exclusiveMinimum", "minimum"), ("exclusiveMaximum", "maximum")] {
+        if map.get(exclusive) == Some(&Value::Bool(true)) {
+            if let Some(bound) = map.shift_remove(bound) {
+                map.insert(exclusive.into(), bound);
+            }
+        } else if map.get(exclusive) == Some(&Value::Bool(false)) {
+            map.shift_remove(exclusive);
+        }
+    }
+    if let Some(Value::Sequence(values)) = map.get("enum") && values.len() == 1 {
+        let value = values[0].clone();
+        map.shift_remove("enum");
+        map.insert("const".into(), value);
+    }
+    if map.shift_remove("nullable") == Some(Value::Bool(true)) {
+        make_nullable(map);
+    }
+    collapse_ref(map);
+}
+
+fn make_nullable(map: &mut Mapping) {
+    if let Some(Value::String(ty)) = map.get("type") {
+        let ty = Value::Sequence(vec![ty.as_str().into(), "null".into()]);
+        map.insert("type".into(), ty);
+        if let Some(Value::Sequence(values)) = map.get_mut("enum") {
+            values.push(Value::Null);
+        }
+        if let Some(value) = map.shift_remove("const") {
+            map.insert("enum".into(), Value::Sequence(vec![value, Value::Null]));
+        }
+        return;
+    }
+    if let Some(Value::Sequence(any_of)) = map.get_mut("anyOf") {
+        any_of.push(null_type());
+        return;
+    }
+    // Move the constraints into the first branch of an anyOf, leaving the annotations in place.
+    let mut inner = Mapping::new();
+    for (key, value) in std::mem::take(map) {
+        if key.as_str().is_some_and(|key| ANNOTATIONS.contains(&key)) {
+            map.insert(key, value);
+        } else {
+            inner.insert(key, value);
+        }
+    }
+    collapse_ref(&mut inner);
+    map.insert("anyOf".into(), Value::Sequence(vec![Value::Mapping(inner), null_type()]));
+}
+
+/// In 3.1, `$ref` can have sibling keywords, so `allOf` is no longer needed to annotate a reference.
+fn collapse_ref(map: &mut Mapping) {
+    let Some(Value::Sequence(all_of)) = map.get("allOf") else {
+        return;
+    };
+    let [Value::Mapping(inner)] = all_of.as_slice() else {
+        return;
+    };
+    if inner.len() != 1 || !inner.contains_key("$ref") {
+        return;
+    }
+    let reference = inner["$ref"].clone();
+    map.shift_remove("allOf");
+    map.insert("$ref".into(), reference);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openapiv3::{SchemaData, SchemaKind};
+    use serde_json::json;
+
+    #[test]
+    fn test_to_openapi_3_1() {
+        let mut spec = OpenAPI::default();
+        let mut name = Schema::new_string();
+        name.data.nullable = true;
+        name.data.example = Some(json!("Alice"));
+        let mut status = Schema::new_str_enum(vec!["active".to_string()]);
+        status.data.nullable = true;
+        let address = Schema {
+            data: SchemaData {
+                nullable: true,
+                description: Some("Home address".to_string()),
+                ..SchemaData::default()
+            },
+            kind: SchemaKind::AllOf { all_of: vec![RefOr::schema_ref("Address")] },
+        };
+        let mut user = Schema::new_object();
+        user.properties_mut().insert("name", name);
+        user.properties_mut().insert("status", status);
+        user.properties_mut().insert("address", address);
+        let mut labels = Schema::new_map(Schema::new_string());
+        labels.data.extensions.insert(KEY_SCHEMA_EXTENSION.to_string(), json!({"type": "string", "pattern": "^[a-z]+$"}));
+        user.properties_mut().insert("labels", labels);
+        spec.schemas.insert("User", user);
+
+        let spec = to_openapi_3_1(&spec);
+        assert_eq!(spec["openapi"], "3.1.0");
+        let properties = &spec["components"]["schemas"]["User"]["properties"];
+        let names = properties.as_mapping().unwrap().keys().collect::<Vec<_>>();
+        assert_eq!(names, ["name", "status", "address", "labels"], "properties keep their order");
+        assert_eq!(*properties, yaml(json!({
+            "name": {"type": ["string", "null"], "examples": ["Alice"]},
+            "status": {"type": ["string", "null"], "enum": ["active", null]},
+            "address": {
+                "description": "Home address",
+                "anyOf": [{"$ref": "#/components/schemas/Address"}, {"type": "null"}],
+            },
+            "labels": {
+                "type": "object",
+                "additionalProperties": {"type": "string"},
+                "propertyNames": {"type": "string", "pattern": "^[a-z]+$"},
+            },
+        })));
+    }
+
+    #[test]
+    fn test_prefix_items() {
+        let mut point = Schema::new_array(Schema::new_any_of(vec![
+            Schema::new_number().into(),
+            Schema::new_string().into(),
+        ]));
+        point.data.extensions.insert(PREFIX_ITEMS_EXTENSION.to_string(), json!(true));
+        let mut spec = OpenAPI::default();
+        spec.schemas.insert("Point", point);
+
+        let v3_0: serde_json::Value = serde_json::from_slice(&to_json(&spec, OpenApiVersion::V3_0).unwrap()).unwrap();
+        let point = &v3_0["components"]["schemas"]["Point"];
+        assert!(point.get(PREFIX_ITEMS_EXTENSION).is_none(), "3.0 output has no x-prefixItems");
+        assert_eq!(point["items"], json!({"anyOf": [{"type": "number"}, {"type": "string"}]}));
+
+        let v3_1 = to_openapi_3_1(&spec);
+        let point = &v3_1["components"]["schemas"]["Point"];
+        assert_eq!(point["prefixItems"], yaml(json!([{"type": "number"}, {"type": "string"}])));
+        assert!(point.get("items").is_none());
+
+        strip_prefix_items(&mut spec);
+        let point = serde_json::to_value(&spec.schemas["Point"]).unwrap();
+        assert!(point.get(PREFIX_ITEMS_EXTENSION).is_none(), "stripped spec has no x-prefixItems");
+        assert_eq!(point["items"], json!({"anyOf": [{"type": "number"}, {"type": "string"}]}));
+    }
+
+    fn yaml(value: serde_json::Value) -> Value {
+        serde_yaml::to_value(value).unwrap()
+    }
+}
@@ -0,0 +1,9 @@
+#[test]
+fn test_decimal() {
+    let t = trybuild::TestCases::new();
+    if cfg!(feature = "decimal_as_number") {
+        t.pass("tests/test-decimal/02-decimal-as-number.rs");
+    } else {
+        t.pass("tests/test-decimal/01-decimal.rs");
+    }
+}
@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use oasgen::{OaSchema, SchemaKind, Type};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+#[derive(OaSchema, Serialize)]
+pub struct Invoice {
+    pub total: Decimal,
+    pub exchange_rate: BigDecimal,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Invoice::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("01-decimal.yaml"));
+
+    // Both crates serialize to strings, which match the documented pattern.
+    let SchemaKind::Type(Type::String(s)) = &Decimal::schema().kind else {
+        panic!("decimals are documented as strings");
+    };
+    let pattern = regex_lite::Regex::new(s.pattern.as_deref().unwrap()).unwrap();
+    let invoice = Invoice {
+        total: Decimal::from_str("-1024.50").unwrap(),
+        exchange_rate: BigDecimal::from_str("1.5e-30").unwrap(),
+    };
+    let value = serde_json::to_value(&invoice).unwrap();
+    for field in ["total", "exchange_rate"] {
+        let value = value[field].as_str().expect("decimals are serialized as strings");
+        assert!(pattern.is_match(value), "{value} matches the pattern");
+    }
+}
@@ -0,0 +1,23 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use oasgen::OaSchema;
+use rust_decimal::Decimal;
+
+#[derive(OaSchema)]
+pub struct Invoice {
+    pub total: Decimal,
+    pub exchange_rate: BigDecimal,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Invoice::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("02-decimal-as-number.yaml"));
+
+    // e.g. with `#[serde(with = "rust_decimal::serde::float")]`
+    let total = Decimal::from_str("-1024.50").unwrap();
+    let total = rust_decimal::serde::float::serialize(&total, serde_json::value::Serializer).unwrap();
+    assert_eq!(total, serde_json::json!(-1024.5));
+}
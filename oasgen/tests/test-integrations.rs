@@ -0,0 +1,9 @@
+#[test]
+fn test_integrations() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/test-integrations/01-formats.rs");
+    t.pass("tests/test-integrations/02-chrono-tz.rs");
+    t.pass("tests/test-integrations/03-collections.rs");
+    t.pass("tests/test-integrations/04-geo-types.rs");
+    t.pass("tests/test-integrations/05-mime.rs");
+}
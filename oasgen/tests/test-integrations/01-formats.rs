@@ -0,0 +1,37 @@
+use oasgen::{json_schema, OaSchema};
+
+#[derive(OaSchema)]
+pub struct Formats {
+    pub url: url::Url,
+    pub email: email_address::EmailAddress,
+    pub version: semver::Version,
+    pub version_req: semver::VersionReq,
+    pub ulid: ulid::Ulid,
+    pub uuid: uuid::Uuid,
+    pub bytes: bytes::Bytes,
+    pub jiff_timestamp: jiff::Timestamp,
+    pub jiff_zoned: jiff::Zoned,
+    pub jiff_date: jiff::civil::Date,
+    pub jiff_span: jiff::Span,
+    pub time_date_time: time::OffsetDateTime,
+    pub time_duration: time::Duration,
+    pub chrono_date_time: chrono::DateTime<chrono::Utc>,
+    pub chrono_date: chrono::NaiveDate,
+    pub chrono_duration: chrono::Duration,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Formats::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("01-formats.yaml"));
+
+    // chrono serializes a duration as a `(seconds, nanoseconds)` tuple.
+    let duration = serde_json::to_value(chrono::Duration::new(90, 5).unwrap()).unwrap();
+    assert_eq!(duration, serde_json::json!([90, 5]));
+    let schema = serde_json::to_value(json_schema::<chrono::Duration>().unwrap()).unwrap();
+    assert_eq!(schema["prefixItems"], serde_json::json!([
+        {"type": "integer", "format": "int64"},
+        {"type": "integer", "format": "int32", "minimum": 0, "maximum": 999_999_999},
+    ]));
+}
@@ -0,0 +1,16 @@
+use oasgen::{OaSchema, SchemaKind, Type, VariantOrUnknownOrEmpty};
+
+fn main() {
+    let schema = chrono_tz::Tz::schema();
+    let SchemaKind::Type(Type::String(s)) = &schema.kind else {
+        panic!("Tz is documented as a string");
+    };
+    assert_eq!(s.format, VariantOrUnknownOrEmpty::Empty);
+    assert_eq!(s.enumeration.len(), chrono_tz::TZ_VARIANTS.len());
+    for name in ["UTC", "Europe/Berlin", "America/Argentina/Buenos_Aires"] {
+        assert!(s.enumeration.iter().any(|tz| tz == name), "{name} is a documented time zone");
+    }
+    // The values are the IANA names, as serialized.
+    let tz = serde_json::to_value(chrono_tz::Europe::Berlin).unwrap();
+    assert_eq!(tz, "Europe/Berlin");
+}
@@ -0,0 +1,15 @@
+use oasgen::OaSchema;
+
+#[derive(OaSchema)]
+pub struct Collections {
+    pub small: smallvec::SmallVec<[u32; 4]>,
+    pub bounded: arrayvec::ArrayVec<u8, 3>,
+    pub code: arrayvec::ArrayString<16>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Collections::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("03-collections.yaml"));
+}
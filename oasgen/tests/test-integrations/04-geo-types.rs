@@ -0,0 +1,29 @@
+use geo_types::{coord, line_string, point, LineString, Point, Polygon};
+use oasgen::OaSchema;
+use serde::Serialize;
+
+#[derive(OaSchema, Serialize)]
+pub struct Area {
+    pub center: Point<f64>,
+    pub border: Polygon<f64>,
+    pub path: LineString<f64>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Area::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("04-geo-types.yaml"));
+
+    // The schema follows geo-types' serde representation.
+    let path = line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)];
+    let area = Area {
+        center: point!(x: 0.5, y: 0.5),
+        border: Polygon::new(path.clone(), vec![]),
+        path,
+    };
+    let value = serde_json::to_value(&area).unwrap();
+    assert_eq!(value["center"], serde_json::json!({"x": 0.5, "y": 0.5}));
+    assert_eq!(value["path"][1], serde_json::to_value(coord! { x: 1.0, y: 1.0 }).unwrap());
+    assert_eq!(value["border"]["interiors"], serde_json::json!([]));
+}
@@ -0,0 +1,22 @@
+use oasgen::OaSchema;
+use serde::Serialize;
+
+mod mime_serde {
+    pub fn serialize<S: serde::Serializer>(mime: &mime::Mime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(mime.essence_str())
+    }
+}
+
+#[derive(OaSchema, Serialize)]
+pub struct Attachment {
+    #[serde(serialize_with = "mime_serde::serialize")]
+    pub content_type: mime::Mime,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let attachment = Attachment { content_type: mime::IMAGE_PNG };
+    assert_eq!(serde_json::to_value(&attachment).unwrap(), serde_json::json!({"content_type": "image/png"}));
+    let schema = serde_json::to_value(Attachment::schema()).unwrap();
+    assert_eq!(schema["properties"]["content_type"], serde_json::json!({"type": "string"}));
+}
@@ -7,4 +7,29 @@ fn run_tests() {
     t.pass("tests/test-none/04-enum.rs");
     t.pass("tests/test-none/05-serde-attrs.rs");
     t.pass("tests/test-none/06-complex-enum.rs");
+    t.pass("tests/test-none/07-schema-names.rs");
+    t.pass("tests/test-none/08-schema-collision.rs");
+    t.pass("tests/test-none/09-components.rs");
+    t.pass("tests/test-none/10-openapi-3-1.rs");
+    t.pass("tests/test-none/11-nullable.rs");
+    t.pass("tests/test-none/12-std.rs");
+    t.pass("tests/test-none/13-map-keys.rs");
+    t.pass("tests/test-none/14-field-overrides.rs");
+    t.pass("tests/test-none/15-recursive.rs");
+    t.pass("tests/test-none/16-enum-docs.rs");
+    t.pass("tests/test-none/17-untagged-other.rs");
+    t.pass("tests/test-none/18-repr-enum.rs");
+    t.pass("tests/test-none/19-container-attrs.rs");
+    t.pass("tests/test-none/20-flatten.rs");
+    t.pass("tests/test-none/21-extensions.rs");
+    t.pass("tests/test-none/22-impl-handlers.rs");
+    t.pass("tests/test-none/23-handler-lookup.rs");
+    if !cfg!(feature = "serde_with") {
+        t.compile_fail("tests/test-none/24-serde-as.rs");
+    }
+    t.pass("tests/test-none/25-generic-recursive.rs");
+    t.compile_fail("tests/test-none/26-unannotated-handler.rs");
+    t.compile_fail("tests/test-none/27-handler-errors.rs");
+    t.compile_fail("tests/test-none/28-repr-enum-overflow.rs");
+    t.pass("tests/test-none/29-variant-names.rs");
 }
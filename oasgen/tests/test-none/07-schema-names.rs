@@ -0,0 +1,51 @@
+use oasgen::{generate_openapi, OaSchema};
+use serde::{Deserialize, Serialize};
+
+mod billing {
+    use super::*;
+
+    #[derive(OaSchema, Serialize, Deserialize)]
+    #[oasgen(qualified_name)]
+    pub struct Account {
+        pub balance: i32,
+    }
+}
+
+mod auth {
+    use super::*;
+
+    #[derive(OaSchema, Serialize, Deserialize)]
+    #[oasgen(qualified_name)]
+    pub struct Account {
+        pub email: String,
+    }
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(rename = "SerdeName")]
+pub struct RenamedBySerde {
+    pub id: i32,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(rename = "Ignored")]
+#[oasgen(rename = "OasgenName")]
+pub enum RenamedByOasgen {
+    A,
+    B,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Foo {
+    billing: billing::Account,
+    auth: auth::Account,
+    serde: RenamedBySerde,
+    oasgen: RenamedByOasgen,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let spec = serde_yaml::to_string(&generate_openapi()).unwrap();
+    let expected = include_str!("07-schema-names.yaml").replace("{crate}", module_path!());
+    assert_eq!(spec.trim(), expected);
+}
@@ -0,0 +1,43 @@
+use oasgen::{generate_openapi, try_generate_openapi, Diagnostic, OaSchema, OasgenError, Server};
+use serde::{Deserialize, Serialize};
+
+mod billing {
+    use super::*;
+
+    #[derive(OaSchema, Serialize, Deserialize)]
+    pub struct Account {
+        pub balance: i32,
+    }
+}
+
+mod auth {
+    use super::*;
+
+    #[derive(OaSchema, Serialize, Deserialize)]
+    pub struct Account {
+        pub email: String,
+    }
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let prefix = module_path!();
+    let collision = Diagnostic::ComponentNameCollision {
+        kind: "schemas",
+        name: "Account".to_string(),
+        modules: vec![format!("{prefix}::auth"), format!("{prefix}::billing")],
+    };
+    let errors = try_generate_openapi().unwrap_err();
+    assert_eq!(errors, vec![OasgenError::Invalid(collision.clone())]);
+
+    // Without `try_`, the registration from the module that sorts first is kept.
+    let spec = serde_json::to_value(generate_openapi()).unwrap();
+    assert!(spec["components"]["schemas"]["Account"]["properties"]["email"].is_object());
+    let server = Server::none();
+    assert_eq!(server.validate(), vec![collision.clone()]);
+    let server = server.freeze();
+    assert_eq!(server.validate(), vec![collision.clone()]);
+
+    let errors = Server::none().try_freeze().err().expect("collisions are errors in try_freeze");
+    assert_eq!(errors, vec![OasgenError::Invalid(collision)]);
+}
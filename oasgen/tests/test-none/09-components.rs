@@ -0,0 +1,51 @@
+use oasgen::{oasgen, Header, OaSchema, Parameter, ParameterSchemaOrContent, Schema, Server};
+use serde::{Deserialize, Serialize};
+
+/// The requested resource does not exist.
+#[derive(Serialize, OaSchema)]
+#[oasgen(response, headers("X-Request-Id"))]
+pub struct NotFound {
+    pub message: String,
+}
+
+/// A new task.
+#[derive(Deserialize, OaSchema)]
+#[oasgen(request_body)]
+pub struct CreateTask {
+    pub title: String,
+}
+
+#[derive(Serialize, OaSchema)]
+pub struct Task {
+    pub id: i32,
+    pub title: String,
+}
+
+oasgen::register_parameter!("Page", || Parameter::query("page", Schema::new_integer()));
+oasgen::register_parameter!("Unused", || Parameter::query("unused", Schema::new_integer()));
+oasgen::register_header!("X-Request-Id", || Header {
+    description: Some("Identifies the request in the logs.".to_string()),
+    style: Default::default(),
+    required: true,
+    deprecated: None,
+    format: ParameterSchemaOrContent::Schema(Schema::new_string().into()),
+    example: None,
+    examples: Default::default(),
+    extensions: Default::default(),
+});
+
+#[oasgen(parameters("Page"), responses(404 = "NotFound"), request_body = "CreateTask", headers("X-Request-Id"))]
+async fn create_task(_body: CreateTask) -> Task {
+    Task { id: 0, title: String::new() }
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none()
+        .post("/tasks", create_task)
+        .validate_on_freeze()
+        .prune_schemas()
+        .freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("09-components.yaml"));
+}
@@ -0,0 +1,31 @@
+use oasgen::{oasgen, to_openapi_3_1, OaSchema, OpenApiVersion, Server};
+use serde::{Deserialize, Serialize};
+
+/// A point on the map.
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Point(f64, f64);
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Place {
+    pub name: Option<String>,
+    pub location: Point,
+}
+
+#[oasgen]
+async fn get_place() -> Place {
+    Place { name: None, location: Point(0.0, 0.0) }
+}
+
+#[oasgen]
+async fn place_created(_body: Place) {}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none()
+        .openapi_version(OpenApiVersion::V3_1)
+        .get("/place", get_place)
+        .webhook("placeCreated", http::Method::POST, place_created)
+        .freeze();
+    let spec = serde_yaml::to_string(&to_openapi_3_1(&server.openapi)).unwrap();
+    assert_eq!(spec.trim(), include_str!("10-openapi-3-1.yaml"));
+}
@@ -0,0 +1,28 @@
+use oasgen::OaSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct User {
+    id: i32,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct UpdateTeam {
+    /// Optional and nullable: absent leaves the manager unchanged, null removes them.
+    /// Documented the same as `Option<User>`, since the schema can't tell absent and null apart.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "::serde_with::rust::double_option")]
+    manager: Option<Option<User>>,
+    /// An `Option<Option<T>>` field and an `Option<T>` one have the same schema.
+    deputy: Option<User>,
+    #[oasgen(required)]
+    owner: Option<User>,
+    #[oasgen(required)]
+    name: Option<String>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = UpdateTeam::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("11-nullable.yaml"));
+}
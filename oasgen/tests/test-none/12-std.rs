@@ -0,0 +1,36 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use oasgen::OaSchema;
+#[derive(OaSchema)]
+pub struct Tag {
+    name: String,
+}
+
+#[derive(OaSchema)]
+pub struct Host {
+    name: &'static str,
+    alias: Cow<'static, str>,
+    tags: HashSet<Tag>,
+    ports: BTreeSet<u16>,
+    labels: BTreeMap<String, String>,
+    queue: VecDeque<Arc<Tag>>,
+    owner: Box<Tag>,
+    rgb: [u8; 3],
+    ip: IpAddr,
+    root: PathBuf,
+    timeout: Duration,
+    marker: PhantomData<Tag>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Host::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("12-std.yaml"));
+}
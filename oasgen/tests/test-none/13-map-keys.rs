@@ -0,0 +1,33 @@
+use std::collections::{BTreeMap, HashMap};
+
+use oasgen::OaSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(OaSchema, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Color {
+    Red,
+    Green,
+}
+
+/// Doesn't implement `OaSchema`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Code(String);
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Palette {
+    names: HashMap<String, String>,
+    by_code: HashMap<Code, String>,
+    #[oasgen(key_schema)]
+    by_color: HashMap<Color, i32>,
+    #[oasgen(key_schema)]
+    by_id: BTreeMap<u32, String>,
+    #[oasgen(explicit_keys)]
+    weights: BTreeMap<Color, f32>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Palette::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("13-map-keys.yaml"));
+}
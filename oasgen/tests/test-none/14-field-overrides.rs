@@ -0,0 +1,47 @@
+use oasgen::{OaSchema, Schema};
+use serde::{Deserialize, Serialize};
+
+/// Types from another crate, which don't implement `OaSchema`.
+mod foreign {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Blob(pub Vec<u8>);
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(transparent)]
+    pub struct Timestamp(pub i64);
+
+    #[derive(Serialize, Deserialize)]
+    pub struct Point {
+        pub x: f64,
+        pub y: f64,
+    }
+}
+
+fn point_schema() -> Schema {
+    let mut schema = Schema::new_array(Schema::new_number());
+    schema.description = Some("[x, y]".to_string());
+    schema
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Attachment {
+    #[oasgen(type = "string", format = "binary")]
+    data: foreign::Blob,
+    #[oasgen(as = i64)]
+    created_at: foreign::Timestamp,
+    #[oasgen(as = Option<i64>)]
+    deleted_at: Option<foreign::Timestamp>,
+    #[oasgen(schema_with = "point_schema")]
+    location: foreign::Point,
+    #[oasgen(type = "object")]
+    metadata: Option<foreign::Point>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Attachment::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("14-field-overrides.yaml"));
+}
@@ -0,0 +1,55 @@
+use oasgen::{OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+/// A tree, referencing itself through `Vec`, `Box` and `Option`.
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Node {
+    pub value: i32,
+    pub children: Vec<Node>,
+    pub parent: Option<Box<Node>>,
+}
+
+/// A tree whose children are inlined, down to the first recursion.
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct InlineNode {
+    pub value: i32,
+    #[oasgen(inline)]
+    pub children: Vec<InlineNode>,
+}
+
+// Mutually recursive types.
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Folder {
+    pub name: String,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub enum Entry {
+    File { name: String },
+    Folder(Box<Folder>),
+}
+
+/// A comment, flattening its reply into itself.
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Comment {
+    pub text: String,
+    #[serde(flatten)]
+    pub reply: Option<Box<Comment>>,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Filesystem {
+    #[oasgen(inline)]
+    pub root: Folder,
+    pub tree: Node,
+    pub inline_tree: InlineNode,
+    pub comment: Comment,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none().freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("15-recursive.yaml"));
+}
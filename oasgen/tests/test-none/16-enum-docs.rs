@@ -0,0 +1,38 @@
+use oasgen::{OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+/// The state of an order.
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    /// Waiting for payment.
+    PendingPayment,
+    /// Paid, and waiting to be shipped.
+    Paid,
+    Shipped,
+}
+
+/// How an order is paid.
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(tag = "method")]
+pub enum Payment {
+    /// Paid by card.
+    Card { last4: String },
+    /// Paid by bank transfer.
+    Transfer { iban: String },
+    /// Paid in cash on delivery.
+    Cash,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Order {
+    pub status: OrderStatus,
+    pub payment: Payment,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none().freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("16-enum-docs.yaml"));
+}
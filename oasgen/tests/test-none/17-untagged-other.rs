@@ -0,0 +1,61 @@
+use oasgen::{OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Limit {
+    Count(u32),
+    Named { name: String },
+    Unlimited,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Nothing {
+    Empty,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    #[serde(alias = "grey")]
+    Gray,
+    Blue,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Destination {
+    Room { room: u32 },
+    Street { street: String },
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum Event {
+    #[serde(alias = "signup")]
+    Created { id: u32 },
+    Deleted { id: u32 },
+    // The tag of a newtype variant, combined with its content in `allOf`, accepts the aliases too.
+    #[serde(alias = "Relocated")]
+    Moved(Destination),
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Settings {
+    pub limit: Limit,
+    pub nothing: Nothing,
+    pub color: Color,
+    pub last_event: Event,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none().freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("17-untagged-other.yaml"));
+}
@@ -0,0 +1,66 @@
+use oasgen::{OaSchema, Server};
+use serde::Serialize;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Priority of a task.
+#[derive(OaSchema, Serialize_repr, Deserialize_repr)]
+#[oasgen(repr)]
+#[repr(u8)]
+pub enum Priority {
+    /// Do it whenever.
+    Low = 1,
+    Medium = 5,
+    /// Do it now.
+    High = 10,
+}
+
+/// Implicit discriminants.
+#[derive(OaSchema, Serialize_repr, Deserialize_repr)]
+#[oasgen(repr)]
+#[repr(i32)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+}
+
+/// Without `#[repr]`, e.g. with a hand-written `Serialize`, the discriminants are `isize`.
+#[derive(OaSchema)]
+#[oasgen(repr)]
+pub enum Flag {
+    Off,
+    On,
+}
+
+/// `#[repr]` alone doesn't change how serde serializes the enum: it's still a string enum.
+#[derive(OaSchema, Serialize)]
+#[repr(u8)]
+pub enum Color {
+    Red = 1,
+    Green = 2,
+}
+
+/// Wide discriminants are fine as long as they fit in an i64.
+#[derive(OaSchema, Serialize_repr)]
+#[oasgen(repr)]
+#[repr(u64)]
+pub enum Size {
+    Small = 1,
+    Huge = i64::MAX as u64,
+}
+
+#[derive(OaSchema)]
+pub struct Task {
+    pub priority: Priority,
+    pub level: Level,
+    pub flag: Flag,
+    pub color: Color,
+    pub size: Size,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none().freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("18-repr-enum.yaml"));
+}
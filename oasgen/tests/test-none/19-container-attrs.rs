@@ -0,0 +1,68 @@
+use oasgen::{OaSchema, Server};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+mod remote {
+    pub struct Timeout {
+        pub secs: u64,
+        pub retries: u32,
+    }
+}
+
+/// A timeout, defined in another crate.
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(remote = "remote::Timeout")]
+pub struct TimeoutDef {
+    pub secs: u64,
+    pub retries: u32,
+}
+
+// The path to a remote definition needn't look like a type.
+#[allow(non_camel_case_types)]
+type timeout_def = TimeoutDef;
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase", deny_unknown_fields)]
+pub enum Shape {
+    Circle { radius_px: u32 },
+    Rect { width_px: u32, height_px: u32 },
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + DeserializeOwned")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: u32,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Pair<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(rename = "AccountSettings")]
+pub struct Settings {
+    pub credentials: Credentials,
+    pub shapes: Page<Shape>,
+    pub range: Pair<u32, u32>,
+    #[serde(with = "TimeoutDef")]
+    pub timeout: remote::Timeout,
+    #[serde(with = "timeout_def")]
+    pub fallback_timeout: remote::Timeout,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none().freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("19-container-attrs.yaml"));
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use oasgen::{OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Address {
+    pub city: String,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Contact {
+    pub email: String,
+    pub address: Address,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Audit {
+    pub created_by: String,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum Payment {
+    Card { number: String },
+    Transfer { iban: String },
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Customer {
+    pub id: u32,
+    #[serde(flatten)]
+    pub contact: Contact,
+    #[serde(flatten)]
+    pub audit: Option<Audit>,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Order {
+    pub id: u32,
+    #[serde(flatten)]
+    pub payment: Payment,
+}
+
+// Merged properties are still denied others, but an `allOf` branch can't deny the properties of the other branches.
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StrictCustomer {
+    pub id: u32,
+    #[serde(flatten)]
+    pub contact: Contact,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StrictOrder {
+    pub id: u32,
+    #[serde(flatten)]
+    pub payment: Payment,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Labels {
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: HashMap<String, String>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none().freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("20-flatten.yaml"));
+}
@@ -0,0 +1,59 @@
+use oasgen::{oasgen, OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Owner {
+    pub name: String,
+}
+
+/// A user account.
+#[derive(OaSchema, Serialize, Deserialize)]
+#[oasgen(
+    title = "User account",
+    external_docs(url = "https://example.com/docs/accounts", description = "Account guide"),
+    extension("x-internal" = false, "x-go-name" = "UserAccount"),
+)]
+pub struct Account {
+    #[oasgen(extension("x-go-name" = "ID"))]
+    pub id: u32,
+    #[oasgen(title = "Owner", extension("x-typescript-type" = "Owner | null"))]
+    pub owner: Owner,
+    #[oasgen(external_docs(url = "https://example.com/docs/tags"))]
+    #[oasgen(extension("x-order" = { "by": "name", "desc": true }))]
+    pub tags: Vec<String>,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+#[oasgen(extension("x-enum-kind" = "status"))]
+pub enum Status {
+    Active,
+    Closed,
+}
+
+#[oasgen(
+    external_docs(url = "https://example.com/docs/get-account"),
+    extension("x-internal" = true, "x-rate-limit" = 10),
+)]
+async fn get_account() -> Account {
+    Account {
+        id: 1,
+        owner: Owner { name: "Ada".to_string() },
+        tags: vec![],
+    }
+}
+
+#[oasgen]
+async fn get_status() -> Status {
+    Status::Active
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none()
+        .get("/account", get_account)
+        .get("/status", get_status)
+        .validate_on_freeze()
+        .freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("21-extensions.yaml"));
+}
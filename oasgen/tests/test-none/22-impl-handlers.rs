@@ -0,0 +1,70 @@
+use oasgen::{oasgen, OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct CreateUser {
+    pub name: String,
+}
+
+pub struct UserHandlers {
+    pub default_name: String,
+}
+
+#[oasgen(tags("users"))]
+impl UserHandlers {
+    /// List all users.
+    #[oasgen]
+    pub async fn list() -> Vec<User> {
+        vec![]
+    }
+
+    #[oasgen(tags("admin"), operation_id = "create_user")]
+    pub async fn create(&self, body: CreateUser) -> User {
+        User { id: 0, name: body.name }
+    }
+
+    #[oasgen(summary = "The default user")]
+    pub fn default_user(&self) -> User {
+        User { id: 0, name: self.default_name.clone() }
+    }
+
+    // Only fns with their own #[oasgen] are handlers.
+    pub async fn load(&self, _id: u32) -> Option<User> {
+        None
+    }
+
+    pub fn helper(&self) -> usize {
+        self.default_name.len()
+    }
+}
+
+// Associated functions without `self` can also be annotated on their own, in a plain impl block.
+impl UserHandlers {
+    #[oasgen]
+    pub async fn guest() -> User {
+        Self::guest_user()
+    }
+
+    fn guest_user() -> User {
+        User { id: 0, name: "guest".to_string() }
+    }
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none()
+        .get("/users", UserHandlers::list)
+        .post("/users", UserHandlers::create)
+        .get("/users/default", UserHandlers::default_user)
+        .get("/users/guest", UserHandlers::guest)
+        .validate_on_freeze()
+        .freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("22-impl-handlers.yaml"));
+}
@@ -0,0 +1,55 @@
+use oasgen::{oasgen, OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Task {
+    pub id: u32,
+}
+
+mod handlers {
+    use oasgen::oasgen;
+
+    use super::Task;
+
+    #[oasgen]
+    pub async fn list_tasks() -> Vec<Task> {
+        vec![]
+    }
+
+    pub struct Admin;
+}
+
+// Generic handlers are documented for each instantiation they're routed with.
+#[oasgen]
+async fn create<T: OaSchema>(body: T) -> T {
+    body
+}
+
+// The handlers of `Admin` are defined away from the type.
+mod admin {
+    use oasgen::oasgen;
+
+    use super::Task;
+
+    #[oasgen(tags("admin"))]
+    impl crate::handlers::Admin {
+        #[oasgen]
+        pub async fn reset() -> Task {
+            Task { id: 0 }
+        }
+    }
+}
+
+pub use handlers::list_tasks as tasks;
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none()
+        .get("/tasks", tasks)
+        .post("/admin/reset", handlers::Admin::reset)
+        .post("/tasks", create::<Task>)
+        .validate_on_freeze()
+        .freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("23-handler-lookup.yaml"));
+}
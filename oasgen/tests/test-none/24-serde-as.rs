@@ -0,0 +1,17 @@
+use oasgen::OaSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+// Without the `serde_with` feature, `serde_as` fields need their schema overridden,
+// otherwise they'd be documented by their own type instead of how they're serialized.
+#[serde_as]
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Upload {
+    #[serde_as(as = "DisplayFromStr")]
+    #[oasgen(as = String)]
+    name: u64,
+    #[serde_as(as = "DisplayFromStr")]
+    size: u64,
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use oasgen::{OaSchema, Server};
+use serde::{Deserialize, Serialize};
+
+// Generic types are inlined, so the recursion ends in a schema accepting any value.
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Tree<T> {
+    pub value: T,
+    pub children: Vec<Tree<T>>,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub enum Expr<T> {
+    Literal(T),
+    Not(Box<Expr<T>>),
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Forest {
+    pub numbers: Tree<i32>,
+    pub names: Tree<String>,
+    pub condition: Expr<bool>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let server = Server::none().freeze();
+    let spec = serde_yaml::to_string(&*server.openapi).unwrap();
+    assert_eq!(spec.trim(), include_str!("25-generic-recursive.yaml"));
+}
@@ -0,0 +1,9 @@
+use oasgen::Server;
+
+async fn list_tasks() -> Vec<u32> {
+    vec![]
+}
+
+fn main() {
+    let _server = Server::none().get("/tasks", list_tasks).freeze();
+}
@@ -0,0 +1,39 @@
+use oasgen::{oasgen, OaSchema};
+use serde::Serialize;
+
+#[derive(OaSchema, Serialize)]
+pub struct User {
+    pub id: u32,
+}
+
+pub struct Connection;
+
+pub struct UserHandlers;
+
+#[oasgen]
+impl UserHandlers {
+    #[oasgen]
+    pub async fn list() -> Vec<User> {
+        vec![]
+    }
+
+    // Not a handler, so its types needn't be documented.
+    pub async fn connect(_url: &str) -> Connection {
+        Connection
+    }
+
+    #[oasgen]
+    pub async fn current() -> Connection {
+        Self::connect("").await
+    }
+}
+
+// The type of `Self` isn't known to #[oasgen] on a single associated function.
+impl User {
+    #[oasgen]
+    pub async fn echo(body: Self) -> Self {
+        body
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use oasgen::OaSchema;
+use serde_repr::Serialize_repr;
+
+#[derive(OaSchema, Serialize_repr)]
+#[oasgen(repr)]
+#[repr(u64)]
+pub enum Size {
+    Small = 1,
+    Huge = u64::MAX,
+}
+
+fn main() {}
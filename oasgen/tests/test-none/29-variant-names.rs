@@ -0,0 +1,29 @@
+use oasgen::OaSchema;
+use serde::{Deserialize, Serialize};
+use oasgen::generate_openapi;
+
+// Variant schemas are named `Enum.Variant`, so they don't collide with a type named `EnumVariant`.
+#[derive(OaSchema, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct ShapeCircle {
+    label: String,
+}
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Drawing {
+    shape: Shape,
+    circle: ShapeCircle,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = generate_openapi();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec, include_str!("29-variant-names.yaml"));
+}
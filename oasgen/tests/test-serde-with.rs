@@ -0,0 +1,5 @@
+#[test]
+fn test_serde_with() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/test-serde-with/01-serde-as.rs");
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use oasgen::OaSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::base64::Base64;
+use serde_with::formats::CommaSeparator;
+use serde_with::{
+    serde_as, DisplayFromStr, DurationSeconds, DurationSecondsWithFrac, OneOrMany, StringWithSeparator,
+    TimestampSeconds,
+};
+
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Tag {
+    name: String,
+}
+
+#[serde_as]
+#[derive(OaSchema, Serialize, Deserialize)]
+pub struct Upload {
+    #[serde_as(as = "DisplayFromStr")]
+    id: u64,
+    #[serde_as(as = "Base64")]
+    content: Vec<u8>,
+    #[serde_as(as = "TimestampSeconds")]
+    created_at: SystemTime,
+    #[serde_as(as = "TimestampSeconds<String>")]
+    updated_at: SystemTime,
+    #[serde_as(as = "DurationSeconds")]
+    ttl: Duration,
+    #[serde_as(as = "DurationSecondsWithFrac")]
+    elapsed: Duration,
+    #[serde_as(as = "StringWithSeparator::<CommaSeparator, String>")]
+    labels: Vec<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    parent_id: Option<u64>,
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    chunk_sizes: Vec<u32>,
+    #[serde_as(as = "HashMap<_, DisplayFromStr>")]
+    checksums: HashMap<String, u128>,
+    #[serde_as(as = "Vec<_>")]
+    tags: Vec<Tag>,
+    // No `OaSchemaAs` impl, so documented as the field's own type.
+    #[serde_as(as = "OneOrMany<_>")]
+    aliases: Vec<String>,
+}
+
+fn main() {
+    use pretty_assertions::assert_eq;
+    let schema = Upload::schema();
+    let spec = serde_yaml::to_string(&schema).unwrap();
+    assert_eq!(spec.trim(), include_str!("01-serde-as.yaml"));
+}